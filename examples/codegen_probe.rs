@@ -0,0 +1,79 @@
+//! Functions whose generated code is inspected by `tests/codegen.rs`.
+//!
+//! The `init_in_place` and `new_boxed` probes construct a 1 MiB `ArrayVec`
+//! and must not copy it through the stack, nor may `emplace_with` copy the
+//! 4 KiB element it builds. `probe_moved_into_box` is the
+//! counterexample that the test uses to make sure that such copies are
+//! actually detected. The remaining probes must reduce to a `memcpy`.
+
+use std::mem::MaybeUninit;
+
+use array_vec::ArrayVec;
+
+type Large = ArrayVec<u8, { 1 << 20 }>;
+
+#[no_mangle]
+#[inline(never)]
+pub fn probe_init_in_place(slot: &mut MaybeUninit<Large>) -> &mut Large {
+    ArrayVec::init_in_place(slot)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn probe_new_boxed() -> Box<Large> {
+    ArrayVec::new_boxed()
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn probe_moved_into_box(data: &[u8]) -> Box<Large> {
+    let mut a = ArrayVec::new();
+    a.extend_from_slice(data);
+    Box::new(a)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn probe_extend_from_slice(a: &mut ArrayVec<u8, 4096>, data: &[u8]) {
+    let _ = a.try_extend_from_slice(data);
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn probe_clone(a: &ArrayVec<u8, 4096>) -> ArrayVec<u8, 4096> {
+    a.clone()
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn probe_clone_from(a: &mut ArrayVec<u8, 4096>, b: &ArrayVec<u8, 4096>) {
+    a.clone_from(b);
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn probe_emplace_with(records: &mut ArrayVec<[u64; 512], 4>, seed: u64) -> &mut [u64; 512] {
+    unsafe {
+        records.emplace_with(|slot| {
+            let record = slot.as_mut_ptr() as *mut u64;
+            for i in 0..512 {
+                record.add(i).write(seed + i as u64);
+            }
+        })
+    }
+}
+
+fn main() {
+    let mut slot = Box::new_uninit();
+    probe_init_in_place(&mut slot).push(1).unwrap();
+    probe_new_boxed().push(2).unwrap();
+    assert_eq!(1, probe_moved_into_box(&[3]).len());
+    let mut a = ArrayVec::new();
+    probe_extend_from_slice(&mut a, &[4, 5]);
+    assert_eq!(a, [4, 5]);
+    let mut b = probe_clone(&a);
+    probe_clone_from(&mut b, &a);
+    assert_eq!(a, b);
+    let mut records = Box::new(ArrayVec::new());
+    assert_eq!(511, probe_emplace_with(&mut records, 0)[511]);
+}
@@ -0,0 +1,122 @@
+//! Comparison and hashing. All of these agree with the implementations for
+//! the slice of the stored elements.
+
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{ArrayVec, LenUint};
+
+// Compares `$lhs` and `$rhs` as `[T]` and `[U]`. The closures turn each side
+// into a slice; `deref` is a shorthand for sides that both deref to one.
+macro_rules! impl_slice_eq {
+    ([$($vars:tt)*] $lhs:ty, $rhs:ty, deref) => {
+        impl_slice_eq! { [$($vars)*] $lhs, $rhs, |a| &**a, |b| &**b }
+    };
+    ([$($vars:tt)*] $lhs:ty, $rhs:ty, |$a:ident| $lhs_slice:expr, |$b:ident| $rhs_slice:expr) => {
+        impl<$($vars)*> PartialEq<$rhs> for $lhs where T: PartialEq<U> {
+            #[inline]
+            fn eq(&self, other: &$rhs) -> bool {
+                let ($a, $b) = (self, other);
+                let (lhs, rhs): (&[T], &[U]) = ($lhs_slice, $rhs_slice);
+                lhs == rhs
+            }
+        }
+    };
+}
+
+impl_slice_eq! {
+    [T, U, const N: usize, const M: usize, L: LenUint, L2: LenUint]
+    ArrayVec<T, N, L>, ArrayVec<U, M, L2>, deref
+}
+impl_slice_eq! { [T, U, const N: usize, L: LenUint] ArrayVec<T, N, L>, [U], |a| &**a, |b| b }
+impl_slice_eq! { [T, U, const N: usize, L: LenUint] [T], ArrayVec<U, N, L>, |a| a, |b| &**b }
+impl_slice_eq! {
+    [T, U, const N: usize, const M: usize, L: LenUint]
+    ArrayVec<T, N, L>, [U; M], |a| &**a, |b| b
+}
+impl_slice_eq! {
+    [T, U, const N: usize, const M: usize, L: LenUint]
+    [T; M], ArrayVec<U, N, L>, |a| a, |b| &**b
+}
+impl_slice_eq! { ['a, T, U, const N: usize, L: LenUint] ArrayVec<T, N, L>, &'a [U], deref }
+impl_slice_eq! { ['a, T, U, const N: usize, L: LenUint] &'a [T], ArrayVec<U, N, L>, deref }
+#[cfg(feature = "alloc")]
+impl_slice_eq! { [T, U, const N: usize, L: LenUint] ArrayVec<T, N, L>, Vec<U>, deref }
+#[cfg(feature = "alloc")]
+impl_slice_eq! { [T, U, const N: usize, L: LenUint] Vec<T>, ArrayVec<U, N, L>, deref }
+
+impl<T: Eq, const N: usize, L: LenUint> Eq for ArrayVec<T, N, L> {}
+
+impl<T, const N: usize, const M: usize, L, L2> PartialOrd<ArrayVec<T, M, L2>> for ArrayVec<T, N, L>
+    where T: PartialOrd, L: LenUint, L2: LenUint
+{
+    #[inline]
+    fn partial_cmp(&self, other: &ArrayVec<T, M, L2>) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<T: Ord, const N: usize, L: LenUint> Ord for ArrayVec<T, N, L> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<T: Hash, const N: usize, L: LenUint> Hash for ArrayVec<T, N, L> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ArrayVec;
+    use core::hash::BuildHasher;
+    use std::collections::hash_map::RandomState;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn equality() {
+        let a: ArrayVec<char, 3> = array_vec!['a', 'b', 'c'];
+        let b: ArrayVec<char, 8> = array_vec!['a', 'b', 'c'];
+        assert_eq!(a, b);
+        assert_eq!(a, ['a', 'b', 'c']);
+        assert_eq!(['a', 'b', 'c'], a);
+        assert_eq!(a, *"abc".chars().collect::<std::vec::Vec<_>>());
+        assert_eq!(&['a', 'b', 'c'][..], a);
+        assert_eq!(a, &['a', 'b', 'c'][..]);
+        assert_ne!(a, ['a', 'b']);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn vec_equality() {
+        let a: ArrayVec<char, 3> = array_vec!['a', 'b', 'c'];
+        assert_eq!(a, vec!['a', 'b', 'c']);
+        assert_eq!(vec!['a', 'b', 'c'], a);
+    }
+
+    #[test]
+    fn ordering() {
+        let a: ArrayVec<char, 3> = array_vec!['a', 'b', 'c'];
+        let mut b: ArrayVec<char, 3> = array_vec!['a', 'b', 'c'];
+        b.pop();
+        assert!(b < a);
+        let c: ArrayVec<char, 8> = array_vec!['a', 'b', 'c'];
+        assert!(c > b);
+        let set: BTreeSet<_> = [a, b].into_iter().collect();
+        assert_eq!(set.into_iter().next().unwrap(), ['a', 'b']);
+    }
+
+    #[test]
+    fn hash_agrees_with_slice() {
+        let state = RandomState::new();
+        let a: ArrayVec<char, 8> = array_vec!['a', 'b', 'c'];
+        assert_eq!(state.hash_one(&a), state.hash_one(&['a', 'b', 'c'][..]));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{ArrayVec, CapacityError, LenUint};
+
+// The error of `try_collect_array_vec`: the full vector and the rejected item.
+type Overflow<T, const N: usize, L = usize> = CapacityError<(ArrayVec<T, N, L>, T)>;
+
+/// Non-panicking alternatives to `Iterator::collect` for `ArrayVec`.
+///
+/// The `FromIterator` implementation of `ArrayVec` panics when the iterator
+/// yields too many elements. This trait is implemented for every iterator
+/// and lets such chains handle overflow instead:
+///
+/// ```
+/// use array_vec::*;
+/// let squares = (1..).map(|x| x * x).collect_array_vec_truncated::<4>();
+/// assert_eq!(&[1, 4, 9, 16], &*squares);
+///
+/// let overflow = (0..10).try_collect_array_vec::<4>().unwrap_err();
+/// let (partial, rejected) = overflow.element();
+/// assert_eq!(&[0, 1, 2, 3], &*partial);
+/// assert_eq!(4, rejected);
+/// ```
+///
+/// The `_with_len` variants also take the length type of the result:
+///
+/// ```
+/// use array_vec::*;
+/// let compact = (0u8..).collect_array_vec_truncated_with_len::<8, u8>();
+/// assert_eq!(9, core::mem::size_of_val(&compact));
+/// ```
+pub trait CollectArrayVec: Iterator + Sized {
+    /// Collects into an `ArrayVec` with capacity `N`. See
+    /// `ArrayVec::try_from_iter`.
+    fn try_collect_array_vec<const N: usize>(self)
+        -> Result<ArrayVec<Self::Item, N>, Overflow<Self::Item, N>>
+    {
+        ArrayVec::try_from_iter(self)
+    }
+
+    /// Collects the first `N` items into an `ArrayVec` with capacity `N`. See
+    /// `ArrayVec::from_iter_truncated`.
+    fn collect_array_vec_truncated<const N: usize>(self) -> ArrayVec<Self::Item, N> {
+        ArrayVec::from_iter_truncated(self)
+    }
+
+    /// Like `try_collect_array_vec`, for an `ArrayVec` with length type `L`.
+    fn try_collect_array_vec_with_len<const N: usize, L: LenUint>(self)
+        -> Result<ArrayVec<Self::Item, N, L>, Overflow<Self::Item, N, L>>
+    {
+        ArrayVec::try_from_iter(self)
+    }
+
+    /// Like `collect_array_vec_truncated`, for an `ArrayVec` with length type
+    /// `L`.
+    fn collect_array_vec_truncated_with_len<const N: usize, L: LenUint>(self)
+        -> ArrayVec<Self::Item, N, L>
+    {
+        ArrayVec::from_iter_truncated(self)
+    }
+}
+
+impl<I: Iterator> CollectArrayVec for I {}
+
+/// A fallible counterpart of `Extend` for fixed-capacity collections.
+///
+/// Unlike `ArrayVec::try_extend_all`, the items appended before an overflow
+/// are kept.
+pub trait TryExtend<A> {
+    /// Appends the items of `iterable` until it is exhausted or the collection
+    /// is full. On overflow, returns `Err` holding the first item that did not
+    /// fit; the iterator is not advanced beyond it.
+    fn try_extend<I>(&mut self, iterable: I) -> Result<(), CapacityError<A>>
+        where I: IntoIterator<Item = A>;
+}
+
+impl<T, const N: usize, L: LenUint> TryExtend<T> for ArrayVec<T, N, L> {
+    fn try_extend<I>(&mut self, iterable: I) -> Result<(), CapacityError<T>>
+        where I: IntoIterator<Item = T>
+    {
+        for element in iterable {
+            self.push(element)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T: Copy + 'a, const N: usize, L: LenUint> TryExtend<&'a T> for ArrayVec<T, N, L> {
+    fn try_extend<I>(&mut self, iterable: I) -> Result<(), CapacityError<&'a T>>
+        where I: IntoIterator<Item = &'a T>
+    {
+        for element in iterable {
+            if self.push(*element).is_err() {
+                return Err(CapacityError::new(element));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn try_from_iter() {
+        let a = ArrayVec::<i32, 4>::try_from_iter(0..4).unwrap();
+        assert_eq!(&[0, 1, 2, 3], &*a);
+
+        let mut source = 0..10;
+        let (partial, rejected) = ArrayVec::<i32, 3>::try_from_iter(&mut source)
+            .unwrap_err()
+            .element();
+        assert_eq!(&[0, 1, 2], &*partial);
+        assert_eq!(3, rejected);
+        assert_eq!(Some(4), source.next());
+    }
+
+    #[test]
+    fn from_iter_truncated() {
+        let mut source = 0..10;
+        let a = ArrayVec::<i32, 3>::from_iter_truncated(&mut source);
+        assert_eq!(&[0, 1, 2], &*a);
+        assert_eq!(Some(3), source.next());
+        let b: ArrayVec<i32, 0> = (0..).collect_array_vec_truncated();
+        assert_eq!(0, b.length());
+    }
+
+    #[test]
+    fn try_extend() {
+        let mut a: ArrayVec<i32, 3> = ArrayVec::new();
+        assert_eq!(Ok(()), a.try_extend(0..1));
+        assert_eq!(Ok(()), a.try_extend(&[1]));
+        assert_eq!(Err(CapacityError::new(&3)), a.try_extend(&[2, 3]));
+        assert_eq!(&[0, 1, 2], &*a);
+        let mut source = 7..10;
+        assert_eq!(Err(CapacityError::new(7)), a.try_extend(&mut source));
+        assert_eq!(Some(8), source.next());
+    }
+
+    #[test]
+    fn collect_chains() {
+        let a = "a,b,c".split(',').try_collect_array_vec::<3>().unwrap();
+        assert_eq!(&["a", "b", "c"], &*a);
+        assert!("a,b,c".split(',').try_collect_array_vec::<2>().is_err());
+
+        let compact = (0..).collect_array_vec_truncated_with_len::<8, u8>();
+        assert!(compact.is_full());
+        let overflow = (0..9).try_collect_array_vec_with_len::<8, u8>().unwrap_err();
+        assert_eq!(compact, overflow.element().0);
+    }
+}
@@ -0,0 +1,136 @@
+//! Compatibility shims for code written against the old
+//! `ArrayVec<T, [T; N]>` shape.
+//!
+//! Older versions of this crate parametrized `ArrayVec` by the type of its
+//! backing array. The `ArrayVec` alias below maps that shape onto the const
+//! generic `ArrayVec<T, N>`, and `LegacyArrayVec` provides the old methods to
+//! code that is generic over the array type:
+//!
+//! ```
+//! use array_vec::compat::{ArrayVec, FixedSizeArray, LegacyArrayVec};
+//!
+//! fn first_two<T, A: FixedSizeArray<T>>(a: T, b: T) -> ArrayVec<T, A> {
+//!     let mut v = ArrayVec::<T, A>::new();
+//!     v.push(a).unwrap();
+//!     v.push(b).unwrap();
+//!     v
+//! }
+//!
+//! let a = first_two::<i32, [i32; 10]>(1, 2);
+//! assert_eq!(10, a.capacity());
+//! ```
+//!
+//! The alias cannot infer the array type from the expected type, so call
+//! sites that wrote `ArrayVec::new()` and relied on inference must either
+//! name the parameters or call `LegacyArrayVec::new()` instead:
+//!
+//! ```
+//! use array_vec::compat::{ArrayVec, LegacyArrayVec};
+//!
+//! let mut a: ArrayVec<i32, [i32; 10]> = ArrayVec::<i32, [i32; 10]>::new();
+//! let mut b: ArrayVec<i32, [i32; 10]> = LegacyArrayVec::new();
+//! a.push(7).unwrap();
+//! b.push(7).unwrap();
+//! assert_eq!(a, b);
+//! ```
+//!
+//! ```compile_fail
+//! use array_vec::compat::ArrayVec;
+//!
+//! // error[E0283]: type annotations needed
+//! let a: ArrayVec<i32, [i32; 10]> = ArrayVec::new();
+//! ```
+
+use core::ops;
+
+use crate::CapacityError;
+
+/// A fixed-size array of elements of type `T`.
+///
+/// This trait is implemented for every `[T; N]` and takes the place of the
+/// unstable `core::array::FixedSizeArray` that earlier versions of this crate
+/// depended on.
+pub trait FixedSizeArray<T> {
+    /// The number of elements in the array.
+    const CAPACITY: usize;
+
+    /// The `ArrayVec` backed by an array of this type.
+    type ArrayVec: LegacyArrayVec<T>
+        + FromIterator<T>
+        + ops::Index<usize, Output = T>
+        + ops::DerefMut<Target = [T]>;
+}
+
+impl<T, const N: usize> FixedSizeArray<T> for [T; N] {
+    const CAPACITY: usize = N;
+    type ArrayVec = crate::ArrayVec<T, N>;
+}
+
+/// `ArrayVec` spelled with its backing array type, e.g. `ArrayVec<T, [T; 8]>`.
+pub type ArrayVec<T, A> = <A as FixedSizeArray<T>>::ArrayVec;
+
+/// The methods of the old `ArrayVec<T, A>`, for code that is generic over
+/// `A: FixedSizeArray<T>`.
+///
+/// The concrete `ArrayVec` has inherent methods with the same names, so this
+/// trait only needs to be imported where the array type is a type parameter.
+pub trait LegacyArrayVec<T>: Sized {
+    /// See `ArrayVec::new`.
+    fn new() -> Self;
+
+    /// See `ArrayVec::capacity`.
+    fn capacity(&self) -> usize;
+
+    /// See `ArrayVec::length`.
+    fn length(&self) -> usize;
+
+    /// See `ArrayVec::push`.
+    fn push(&mut self, x: T) -> Result<(), CapacityError<T>>;
+
+    /// See `ArrayVec::pop`.
+    fn pop(&mut self) -> Option<T>;
+}
+
+impl<T, const N: usize> LegacyArrayVec<T> for crate::ArrayVec<T, N> {
+    fn new() -> Self { crate::ArrayVec::new() }
+
+    fn capacity(&self) -> usize { crate::ArrayVec::capacity(self) }
+
+    fn length(&self) -> usize { crate::ArrayVec::length(self) }
+
+    fn push(&mut self, x: T) -> Result<(), CapacityError<T>> { crate::ArrayVec::push(self, x) }
+
+    fn pop(&mut self) -> Option<T> { crate::ArrayVec::pop(self) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn capacity_of<T, A: FixedSizeArray<T>>() -> usize { A::CAPACITY }
+
+    // Shaped like code written against the old `ArrayVec<T, A>`.
+    fn fill<T: Clone, A: FixedSizeArray<T>>(x: T) -> ArrayVec<T, A> {
+        let mut a = ArrayVec::<T, A>::new();
+        while a.length() < a.capacity() {
+            a.push(x.clone()).unwrap();
+        }
+        a
+    }
+
+    #[test]
+    fn legacy_shape() {
+        let mut a: ArrayVec<i32, [i32; 4]> = ArrayVec::<i32, [i32; 4]>::new();
+        a.push(3).unwrap();
+        let b: crate::ArrayVec<i32, 4> = a;
+        assert_eq!(1, b.length());
+        assert_eq!(4, capacity_of::<i32, [i32; 4]>());
+
+        let inferred: ArrayVec<i32, [i32; 2]> = LegacyArrayVec::new();
+        assert_eq!(2, inferred.capacity());
+
+        let c = fill::<i32, [i32; 3]>(7);
+        assert_eq!(&[7, 7, 7], &*c);
+        assert_eq!(7, c[2]);
+    }
+}
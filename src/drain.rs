@@ -0,0 +1,194 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ptr::{self, NonNull};
+use core::slice;
+
+use crate::{ArrayVec, LenUint};
+
+/// A draining iterator for `ArrayVec`, created by `ArrayVec::drain`.
+///
+/// The elements that are not yielded are dropped together with the iterator,
+/// and the elements after the drained range are then shifted back to close
+/// the gap. If the iterator is leaked (e.g. with `mem::forget`), the vector
+/// is left truncated to the start of the range and the remaining elements are
+/// leaked.
+pub struct Drain<'a, T, const N: usize, L: LenUint = usize> {
+    // The vector's length has been set to the start of the drained range, so
+    // it never observes the elements owned by this iterator.
+    pub(crate) vec: NonNull<ArrayVec<T, N, L>>,
+    // Indices of the elements that have not been yielded yet.
+    next: usize,
+    end: usize,
+    pub(crate) tail_start: usize,
+    pub(crate) tail_len: usize,
+    phantom: PhantomData<&'a mut ArrayVec<T, N, L>>,
+}
+
+impl<'a, T, const N: usize, L: LenUint> Drain<'a, T, N, L> {
+    pub(crate) fn new(vec: &'a mut ArrayVec<T, N, L>, start: usize, end: usize) -> Self {
+        let len = vec.len();
+        vec.store_len(start);
+        Drain {
+            vec: NonNull::from(vec),
+            next: start,
+            end,
+            tail_start: end,
+            tail_len: len - end,
+            phantom: PhantomData,
+        }
+    }
+
+    fn base_ptr(&self) -> *mut T {
+        unsafe { (*self.vec.as_ptr()).as_mut_ptr() }
+    }
+
+    /// Returns the elements that have not been yielded yet as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.base_ptr().add(self.next), self.end - self.next) }
+    }
+}
+
+// `Drain` behaves like the `&mut ArrayVec` it was created from, so it may be
+// sent or shared between threads whenever the elements can.
+unsafe impl<T: Send, const N: usize, L: LenUint> Send for Drain<'_, T, N, L> {}
+unsafe impl<T: Sync, const N: usize, L: LenUint> Sync for Drain<'_, T, N, L> {}
+
+impl<T, const N: usize, L: LenUint> Iterator for Drain<'_, T, N, L> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.next == self.end {
+            return None;
+        }
+        let x = unsafe { ptr::read(self.base_ptr().add(self.next)) };
+        self.next += 1;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.next;
+        (n, Some(n))
+    }
+}
+
+impl<T, const N: usize, L: LenUint> DoubleEndedIterator for Drain<'_, T, N, L> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.next == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(unsafe { ptr::read(self.base_ptr().add(self.end)) })
+    }
+}
+
+impl<T, const N: usize, L: LenUint> ExactSizeIterator for Drain<'_, T, N, L> {}
+
+impl<T, const N: usize, L: LenUint> FusedIterator for Drain<'_, T, N, L> {}
+
+impl<T: fmt::Debug, const N: usize, L: LenUint> fmt::Debug for Drain<'_, T, N, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize, L: LenUint> Drop for Drain<'_, T, N, L> {
+    fn drop(&mut self) {
+        // Moves the tail back even if one of the destructors below panics.
+        struct MoveTail<'r, 'a, T, const N: usize, L: LenUint>(&'r mut Drain<'a, T, N, L>);
+
+        impl<T, const N: usize, L: LenUint> Drop for MoveTail<'_, '_, T, N, L> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                unsafe {
+                    let vec = drain.vec.as_mut();
+                    let start = vec.len();
+                    if drain.tail_start != start {
+                        let base = vec.as_mut_ptr();
+                        ptr::copy(base.add(drain.tail_start), base.add(start), drain.tail_len);
+                    }
+                    vec.store_len(start + drain.tail_len);
+                }
+            }
+        }
+
+        let guard = MoveTail(self);
+        let drain = &mut *guard.0;
+        unsafe {
+            let remaining = ptr::slice_from_raw_parts_mut(drain.base_ptr().add(drain.next),
+                                                          drain.end - drain.next);
+            drain.next = drain.end;
+            ptr::drop_in_place(remaining);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ArrayVec;
+    use core::mem;
+    use std::rc::Rc;
+
+    #[test]
+    fn drain_middle() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5];
+        let mut d = a.drain(1..4);
+        assert_eq!(3, d.len());
+        assert_eq!(&[1, 2, 3], d.as_slice());
+        assert_eq!(Some(1), d.next());
+        assert_eq!(Some(3), d.next_back());
+        assert_eq!(1, d.len());
+        mem::drop(d);
+        assert_eq!(&[0, 4, 5], &*a);
+    }
+
+    #[test]
+    fn drain_all() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5];
+        let drained: std::vec::Vec<i32> = a.drain(..).rev().collect();
+        assert_eq!(drained, [5, 4, 3, 2, 1, 0]);
+        assert_eq!(0, a.length());
+        a.push(9).unwrap();
+        assert_eq!(0, a.drain(1..).count());
+        assert_eq!(&[9], &*a);
+    }
+
+    #[test]
+    fn drain_drops_remaining() {
+        let rc = Rc::new(());
+        let mut a: ArrayVec<Rc<()>, 4> = ArrayVec::new();
+        a.resize(4, rc.clone());
+        let mut d = a.drain(..=2);
+        mem::drop(d.next());
+        mem::drop(d);
+        assert_eq!(1, a.length());
+        assert_eq!(2, Rc::strong_count(&rc));
+    }
+
+    #[test]
+    fn drain_forget() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5];
+        mem::forget(a.drain(2..3));
+        assert_eq!(&[0, 1], &*a);
+        a.push(7).unwrap();
+        assert_eq!(&[0, 1, 7], &*a);
+    }
+
+    #[test]
+    fn drain_send_sync() {
+        fn assert_send_sync<X: Send + Sync>(_: &X) {}
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5];
+        let d = a.drain(..);
+        assert_send_sync(&d);
+        assert_send_sync(&ArrayVec::<i32, 8>::new().splice(.., [1, 2]));
+        let sum = std::thread::scope(|s| s.spawn(move || d.sum::<i32>()).join().unwrap());
+        assert_eq!(15, sum);
+    }
+
+    #[test]
+    #[should_panic]
+    fn drain_out_of_bounds() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5];
+        a.drain(3..7);
+    }
+}
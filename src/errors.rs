@@ -0,0 +1,60 @@
+use core::fmt;
+
+/// Error returned when an element does not fit into an `ArrayVec`.
+///
+/// The element that could not be inserted is handed back to the caller and
+/// can be retrieved with `element()`. Operations that reject more than a
+/// single element use `CapacityError<()>`.
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+pub struct CapacityError<T = ()> {
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    /// Creates a new `CapacityError` carrying the rejected `element`.
+    pub const fn new(element: T) -> Self {
+        CapacityError { element }
+    }
+
+    /// Returns the element that could not be inserted.
+    pub fn element(self) -> T {
+        self.element
+    }
+
+    /// Discards the element, keeping only the information that an insertion
+    /// failed.
+    pub fn simplify(self) -> CapacityError {
+        CapacityError { element: () }
+    }
+}
+
+const CAPERROR: &str = "insufficient capacity";
+
+impl<T> fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CapacityError: {}", CAPERROR)
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(CAPERROR)
+    }
+}
+
+impl<T> core::error::Error for CapacityError<T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::string::{String, ToString};
+
+    #[test]
+    fn element_round_trip() {
+        let e = CapacityError::new(String::from("spilled"));
+        assert_eq!("insufficient capacity", e.to_string());
+        assert_eq!("CapacityError: insufficient capacity", format!("{:?}", e));
+        assert_eq!(CapacityError::new(()), e.clone().simplify());
+        assert_eq!("spilled", e.element());
+    }
+}
@@ -0,0 +1,141 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ptr;
+
+use crate::{ArrayVec, LenUint};
+
+/// An iterator that removes the elements matching a predicate, created by
+/// `ArrayVec::extract_if`.
+///
+/// The remaining elements are compacted as the iterator advances. If the
+/// iterator is dropped before it is exhausted, the elements it has not
+/// visited yet are kept. If it is leaked (e.g. with `mem::forget`), the
+/// vector is left truncated to the start of the range and the remaining
+/// elements are leaked.
+pub struct ExtractIf<'a, T, F, const N: usize, L: LenUint = usize> where F: FnMut(&mut T) -> bool {
+    // The vector's length has been set to the start of the range while this
+    // iterator is alive.
+    vec: &'a mut ArrayVec<T, N, L>,
+    // Index of the next element to be visited.
+    idx: usize,
+    // End of the range that is being visited.
+    end: usize,
+    // Number of elements removed so far.
+    del: usize,
+    old_len: usize,
+    pred: F,
+}
+
+impl<'a, T, F, const N: usize, L: LenUint> ExtractIf<'a, T, F, N, L>
+    where F: FnMut(&mut T) -> bool
+{
+    pub(crate) fn new(vec: &'a mut ArrayVec<T, N, L>, start: usize, end: usize, pred: F) -> Self {
+        let old_len = vec.len();
+        vec.store_len(start);
+        ExtractIf { vec, idx: start, end, del: 0, old_len, pred }
+    }
+}
+
+impl<T, F, const N: usize, L: LenUint> Iterator for ExtractIf<'_, T, F, N, L>
+    where F: FnMut(&mut T) -> bool
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let base = self.vec.as_mut_ptr();
+        while self.idx < self.end {
+            let i = self.idx;
+            // If the predicate panics, `idx` is not advanced and the element
+            // is kept by `drop`.
+            let extracted = (self.pred)(unsafe { &mut *base.add(i) });
+            self.idx += 1;
+            if extracted {
+                self.del += 1;
+                return Some(unsafe { ptr::read(base.add(i)) });
+            } else if self.del > 0 {
+                unsafe { ptr::copy_nonoverlapping(base.add(i), base.add(i - self.del), 1) };
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.idx))
+    }
+}
+
+impl<T, F, const N: usize, L: LenUint> FusedIterator for ExtractIf<'_, T, F, N, L>
+    where F: FnMut(&mut T) -> bool {}
+
+impl<T, F, const N: usize, L: LenUint> fmt::Debug for ExtractIf<'_, T, F, N, L>
+    where T: fmt::Debug, F: FnMut(&mut T) -> bool
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let base = self.vec.as_ptr();
+        let peek = unsafe {
+            core::slice::from_raw_parts(base.add(self.idx), self.end - self.idx)
+        };
+        f.debug_tuple("ExtractIf").field(&peek).finish()
+    }
+}
+
+impl<T, F, const N: usize, L: LenUint> Drop for ExtractIf<'_, T, F, N, L>
+    where F: FnMut(&mut T) -> bool
+{
+    fn drop(&mut self) {
+        unsafe {
+            if self.idx < self.old_len && self.del > 0 {
+                let base = self.vec.as_mut_ptr();
+                ptr::copy(base.add(self.idx), base.add(self.idx - self.del),
+                          self.old_len - self.idx);
+            }
+        }
+        self.vec.store_len(self.old_len - self.del);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ArrayVec;
+    use core::mem;
+    use std::vec::Vec;
+
+    #[test]
+    fn extract_evens() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5, 6, 7];
+        let evens: Vec<i32> = a.extract_if(.., |x| *x % 2 == 0).collect();
+        assert_eq!(evens, [0, 2, 4, 6]);
+        assert_eq!(&[1, 3, 5, 7], &*a);
+    }
+
+    #[test]
+    fn extract_range_partially() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5, 6, 7];
+        let mut it = a.extract_if(2..6, |x| *x % 2 == 1);
+        assert_eq!(Some(3), it.next());
+        mem::drop(it);
+        assert_eq!(&[0, 1, 2, 4, 5, 6, 7], &*a);
+    }
+
+    #[test]
+    fn extract_forget() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5, 6, 7];
+        let mut it = a.extract_if(3.., |_| true);
+        assert_eq!(Some(3), it.next());
+        mem::forget(it);
+        assert_eq!(&[0, 1, 2], &*a);
+    }
+
+    #[test]
+    fn extract_predicate_panics() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5, 6, 7];
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            a.extract_if(.., |x| {
+                assert!(*x != 4);
+                *x == 1
+            }).for_each(mem::drop);
+        }));
+        assert!(result.is_err());
+        assert_eq!(&[0, 2, 3, 4, 5, 6, 7], &*a);
+    }
+}
@@ -0,0 +1,164 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ptr;
+use core::slice;
+
+use crate::{ArrayVec, LenUint};
+
+/// An iterator that moves out of an `ArrayVec`, created by its
+/// `IntoIterator` implementation.
+///
+/// Dropping the iterator drops the elements that have not been yielded yet.
+pub struct IntoIter<T, const N: usize, L: LenUint = usize> {
+    // The length of `vec` is kept at zero, so it never drops the elements
+    // owned by this iterator.
+    vec: ArrayVec<T, N, L>,
+    // Indices of the elements that have not been yielded yet.
+    start: usize,
+    end: usize,
+}
+
+impl<T, const N: usize, L: LenUint> IntoIter<T, N, L> {
+    pub(crate) fn new(mut vec: ArrayVec<T, N, L>) -> Self {
+        let end = vec.len();
+        vec.store_len(0);
+        IntoIter { vec, start: 0, end }
+    }
+
+    /// Returns the elements that have not been yielded yet as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            slice::from_raw_parts(self.vec.as_ptr().add(self.start), self.end - self.start)
+        }
+    }
+
+    /// Returns the elements that have not been yielded yet as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe {
+            slice::from_raw_parts_mut(self.vec.as_mut_ptr().add(self.start),
+                                      self.end - self.start)
+        }
+    }
+}
+
+impl<T, const N: usize, L: LenUint> Iterator for IntoIter<T, N, L> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        let x = unsafe { ptr::read(self.vec.as_ptr().add(self.start)) };
+        self.start += 1;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.start;
+        (n, Some(n))
+    }
+}
+
+impl<T, const N: usize, L: LenUint> DoubleEndedIterator for IntoIter<T, N, L> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(unsafe { ptr::read(self.vec.as_ptr().add(self.end)) })
+    }
+}
+
+impl<T, const N: usize, L: LenUint> ExactSizeIterator for IntoIter<T, N, L> {}
+
+impl<T, const N: usize, L: LenUint> FusedIterator for IntoIter<T, N, L> {}
+
+impl<T: fmt::Debug, const N: usize, L: LenUint> fmt::Debug for IntoIter<T, N, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize, L: LenUint> Drop for IntoIter<T, N, L> {
+    fn drop(&mut self) {
+        let remaining: *mut [T] = self.as_mut_slice();
+        self.start = self.end;
+        unsafe { ptr::drop_in_place(remaining) };
+    }
+}
+
+impl<T, const N: usize, L: LenUint> IntoIterator for ArrayVec<T, N, L> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N, L>;
+
+    fn into_iter(self) -> IntoIter<T, N, L> {
+        IntoIter::new(self)
+    }
+}
+
+impl<'a, T, const N: usize, L: LenUint> IntoIterator for &'a ArrayVec<T, N, L> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize, L: LenUint> IntoIterator for &'a mut ArrayVec<T, N, L> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ArrayVec;
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    #[test]
+    fn by_value() {
+        let a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4];
+        let mut collected = Vec::new();
+        for x in a.clone() {
+            collected.push(x);
+        }
+        assert_eq!(collected, [0, 1, 2, 3, 4]);
+
+        let mut it = a.into_iter();
+        assert_eq!(Some(4), it.next_back());
+        assert_eq!(Some(0), it.next());
+        assert_eq!(3, it.len());
+        it.as_mut_slice()[0] = 10;
+        assert_eq!(&[10, 2, 3], it.as_slice());
+        assert_eq!(it.rev().collect::<Vec<_>>(), [3, 2, 10]);
+    }
+
+    #[test]
+    fn by_reference() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4];
+        for x in &mut a {
+            *x *= 2;
+        }
+        let sum: i32 = (&a).into_iter().sum();
+        assert_eq!(20, sum);
+    }
+
+    #[test]
+    fn drops_only_remaining() {
+        let rc = Rc::new(());
+        let mut a: ArrayVec<Rc<()>, 4> = ArrayVec::new();
+        a.resize(4, rc.clone());
+        let mut it = a.into_iter();
+        let first = it.next().unwrap();
+        assert_eq!(5, Rc::strong_count(&rc));
+        drop(it);
+        assert_eq!(2, Rc::strong_count(&rc));
+        drop(first);
+        assert_eq!(1, Rc::strong_count(&rc));
+    }
+}
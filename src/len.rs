@@ -0,0 +1,60 @@
+use core::num::{NonZeroU16, NonZeroU32, NonZeroU8, NonZeroUsize};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type that stores the length of an `ArrayVec`.
+///
+/// This trait is implemented for `u8`, `u16`, `u32` and `usize`, and cannot be
+/// implemented outside of this crate. A smaller length type makes the vector
+/// smaller, but limits its capacity to `MAX_CAPACITY`, which is checked at
+/// compile time.
+///
+/// The length is stored with an offset of one in a non-zero integer, so that
+/// `Option<ArrayVec<..>>` takes no more space than the vector itself. This is
+/// why `MAX_CAPACITY` is one less than the largest value of the type.
+///
+/// ```compile_fail
+/// use array_vec::ArrayVec;
+///
+/// // `u8::MAX_CAPACITY` is 254.
+/// let a: ArrayVec<u8, 255, u8> = ArrayVec::new();
+/// ```
+pub trait LenUint: sealed::Sealed + Copy + 'static {
+    /// The largest capacity of an `ArrayVec` that uses this length type.
+    const MAX_CAPACITY: usize;
+
+    /// The in-memory representation of the length: a non-zero integer of the
+    /// same size as `Self`, holding the length plus one.
+    #[doc(hidden)]
+    type Stored: Copy;
+
+    /// The representation of length zero.
+    #[doc(hidden)]
+    const EMPTY: Self::Stored;
+}
+
+macro_rules! impl_len_uint {
+    ($($t:ty => $stored:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl LenUint for $t {
+                const MAX_CAPACITY: usize = {
+                    let max = <$t>::MAX as u128 - 1;
+                    if max > usize::MAX as u128 { usize::MAX } else { max as usize }
+                };
+                type Stored = $stored;
+                const EMPTY: $stored = <$stored>::MIN;
+            }
+        )*
+    }
+}
+
+impl_len_uint! {
+    u8 => NonZeroU8,
+    u16 => NonZeroU16,
+    u32 => NonZeroU32,
+    usize => NonZeroUsize
+}
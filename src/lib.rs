@@ -1,131 +1,1122 @@
-#![feature(no_std)]
 #![no_std]
-#![feature(core)]
 
 // std is needed for tests
 #[cfg(test)] #[macro_use] extern crate std;
 
-#[macro_use] extern crate core;
-
-use core::prelude::*;
+#[cfg(feature = "alloc")] extern crate alloc;
 
 use core::mem;
+use core::ptr;
 use core::slice;
 use core::ops;
 use core::fmt;
 
-use core::array::FixedSizeArray;
+use core::borrow::{Borrow, BorrowMut};
 use core::fmt::{Debug,Formatter};
-use core::iter::FromIterator;
+use core::iter::{self, FromIterator};
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[macro_use] mod macros;
+
+pub mod compat;
+pub mod policy;
+mod cmp;
+mod collect;
+mod drain;
+mod errors;
+mod extract_if;
+mod into_iter;
+mod len;
+mod splice;
+
+pub use collect::{CollectArrayVec, TryExtend};
+pub use drain::Drain;
+pub use errors::CapacityError;
+pub use len::LenUint;
+pub use extract_if::ExtractIf;
+pub use into_iter::IntoIter;
+pub use policy::{OverflowPolicy, PolicyVec};
+pub use splice::Splice;
+
+// The error of `try_extend_all`: the items that were appended before the
+// overflow and an iterator over the rest.
+type Rejected<T, I, const N: usize, L> =
+    CapacityError<(ArrayVec<T, N, L>, iter::Chain<iter::Once<T>, I>)>;
 
 /// An alternative to `Vec<T>` that uses an embedded fixed-size array to store
 /// its elements, thus avoiding heap allocation.
 ///
-/// The type parameter `A` must be a fixed-size array of elements of type `T`.
 /// The number of elements that can be stored by this vector is bounded by the
-/// size of `A`.
+/// const parameter `N`, which is also available as `ArrayVec::CAPACITY`.
+/// This holds for zero-sized element types as well, which never occupy any
+/// memory but are still counted, dropped and yielded like any other element.
+///
+/// The length is stored in an integer of type `L`, which defaults to `usize`.
+/// Choosing a smaller `LenUint` type such as `u8` shrinks the vector, but
+/// limits its capacity to `L::MAX_CAPACITY`; a larger `N` fails to compile.
+/// `Option<ArrayVec<T, N, L>>` is always the same size as the vector itself.
 ///
 /// # Examples
 ///
 /// ```
 /// use array_vec::*;
-/// let mut a: ArrayVec<i32, [_; 10]> = ArrayVec::new();
-/// a.push(7);
+/// let mut a: ArrayVec<i32, 10> = ArrayVec::new();
+/// a.push(7).unwrap();
 /// assert_eq!(Some(7), a.pop());
+///
+/// let b = array_vec![1, 2, 3; 10];
+/// assert_eq!(b, [1, 2, 3]);
+///
+/// let small: ArrayVec<u8, 8, u8> = ArrayVec::new();
+/// assert_eq!(9, core::mem::size_of_val(&small));
+/// assert_eq!(9, core::mem::size_of::<Option<ArrayVec<u8, 8, u8>>>());
 /// ```
-pub struct ArrayVec<T, A: FixedSizeArray<T>> {
-    array: Option<A>, // is `None` only during destruction, see `impl Drop`
-    idx: usize,
-    phantom: core::marker::PhantomData<T>
+pub struct ArrayVec<T, const N: usize, L: LenUint = usize> {
+    // Only the first `len()` elements of `array` are initialized.
+    array: [MaybeUninit<T>; N],
+    // The length plus one, see `LenUint`. Use `len()` and `store_len()`.
+    len: L::Stored,
 }
 
-impl<T, A: FixedSizeArray<T>> ArrayVec<T, A> {
-    unsafe fn base_ptr_mut(&mut self) -> *mut T {
-        if let &mut Some(ref mut ref_arr) = &mut self.array {
-            return mem::transmute(ref_arr as *mut A)
-        }
-        unreachable!();
+impl<T, const N: usize, L: LenUint> ArrayVec<T, N, L> {
+    /// The maximal amount of elements that can be stored in this vector.
+    pub const CAPACITY: usize = N;
+
+    // Evaluated by every constructor, so that a capacity that does not fit
+    // into the length type is rejected at compile time.
+    const CAPACITY_FITS: () =
+        assert!(N <= L::MAX_CAPACITY, "ArrayVec: capacity exceeds L::MAX_CAPACITY");
+
+    // The methods of `LenUint` cannot be called in a `const fn`, so `len()`
+    // and `store_len()` access the stored length through an unsigned integer
+    // of the same size instead.
+
+    /// Returns the number of elements currently stored in this vector. Same
+    /// as `length()`.
+    pub const fn len(&self) -> usize {
+        let p = &self.len as *const L::Stored;
+        let stored = unsafe {
+            match mem::size_of::<L>() {
+                1 => *(p as *const u8) as usize,
+                2 => *(p as *const u16) as usize,
+                4 => *(p as *const u32) as usize,
+                _ => *(p as *const usize),
+            }
+        };
+        stored - 1
     }
 
-    unsafe fn base_ptr(&self) -> *const T {
-        if let &Some(ref ref_arr) = &self.array {
-            return mem::transmute(ref_arr as *const A)
+    /// Sets the length without any other bookkeeping. The first `len`
+    /// elements must be initialized.
+    const fn store_len(&mut self, len: usize) {
+        debug_assert!(len <= N);
+        let stored = len + 1;
+        let p = &mut self.len as *mut L::Stored;
+        unsafe {
+            match mem::size_of::<L>() {
+                1 => *(p as *mut u8) = stored as u8,
+                2 => *(p as *mut u16) = stored as u16,
+                4 => *(p as *mut u32) = stored as u32,
+                _ => *(p as *mut usize) = stored,
+            }
         }
-        unreachable!();
     }
 
     /// Create an empty `ArrayVec`.
-    pub fn new() -> Self {
+    ///
+    /// This is a `const fn`, so it can be used to initialize `static` and
+    /// `const` items:
+    ///
+    /// ```
+    /// use array_vec::*;
+    /// static SQUARES: ArrayVec<u32, 8> = {
+    ///     let mut table = ArrayVec::new();
+    ///     let mut i = 0;
+    ///     while !table.is_full() {
+    ///         if table.push(i * i).is_err() {
+    ///             unreachable!();
+    ///         }
+    ///         i += 1;
+    ///     }
+    ///     table
+    /// };
+    /// assert_eq!(49, SQUARES[7]);
+    /// ```
+    ///
+    /// Since the destructor of `ArrayVec` cannot run at compile time, vectors
+    /// that are not part of the final value must be disposed of with
+    /// `mem::forget` in constant expressions.
+    pub const fn new() -> Self {
+        let () = Self::CAPACITY_FITS;
         ArrayVec {
-            array: Some(unsafe { mem::uninitialized() }),
-            idx: 0,
-            phantom: core::marker::PhantomData
+            array: [const { MaybeUninit::uninit() }; N],
+            len: L::EMPTY,
+        }
+    }
+
+    /// Initializes an empty `ArrayVec` in `slot` and returns a reference to
+    /// it.
+    ///
+    /// Unlike `new()`, this never moves the vector, so the embedded array is
+    /// not copied through the stack. Only the length is written; the storage
+    /// is left uninitialized. Any previous value in `slot` is overwritten
+    /// without being dropped.
+    ///
+    /// ```
+    /// use array_vec::*;
+    /// use core::mem::MaybeUninit;
+    /// static mut SLOT: MaybeUninit<ArrayVec<u8, 65536>> = MaybeUninit::uninit();
+    ///
+    /// let buffer = ArrayVec::init_in_place(unsafe { &mut *core::ptr::addr_of_mut!(SLOT) });
+    /// buffer.push(1).unwrap();
+    /// assert_eq!(1, buffer.len());
+    /// ```
+    pub const fn init_in_place(slot: &mut MaybeUninit<Self>) -> &mut Self {
+        let () = Self::CAPACITY_FITS;
+        let p = slot.as_mut_ptr();
+        unsafe {
+            ptr::addr_of_mut!((*p).len).write(L::EMPTY);
+            &mut *p
+        }
+    }
+
+    /// Creates an empty `ArrayVec` on the heap, without copying the embedded
+    /// array through the stack as `Box::new(ArrayVec::new())` might.
+    #[cfg(feature = "alloc")]
+    pub fn new_boxed() -> Box<Self> {
+        let mut boxed = Box::<Self>::new_uninit();
+        Self::init_in_place(&mut boxed);
+        unsafe { boxed.assume_init() }
+    }
+
+    /// Creates an `ArrayVec` holding the elements of `items`. Used by
+    /// `array_vec!`; fails to compile if `K` exceeds the capacity.
+    #[doc(hidden)]
+    pub const fn __from_items<const K: usize>(items: [T; K]) -> Self {
+        const { assert!(K <= N, "array_vec!: too many elements for the capacity") };
+        let mut result = Self::new();
+        let items = ManuallyDrop::new(items);
+        unsafe {
+            let src = &items as *const ManuallyDrop<[T; K]> as *const T;
+            ptr::copy_nonoverlapping(src, result.as_mut_ptr(), K);
         }
+        result.store_len(K);
+        result
+    }
+
+    /// Creates a full `ArrayVec` holding the elements of `array`.
+    pub const fn from_array(array: [T; N]) -> Self {
+        let array = ManuallyDrop::new(array);
+        // `[T; N]` and `[MaybeUninit<T>; N]` have the same layout, and
+        // `ManuallyDrop` keeps the elements from being dropped twice.
+        let src = &array as *const ManuallyDrop<[T; N]> as *const [MaybeUninit<T>; N];
+        let mut result = Self::new();
+        result.array = unsafe { ptr::read(src) };
+        result.store_len(N);
+        result
+    }
+
+    /// Collects the items of `iterable` into a new vector.
+    ///
+    /// If there are more items than fit, returns `Err` holding the vector
+    /// filled up to its capacity and the first item that did not fit. The
+    /// iterator is not advanced beyond that item.
+    pub fn try_from_iter<I>(iterable: I) -> Result<Self, CapacityError<(Self, T)>>
+        where I: IntoIterator<Item = T>
+    {
+        let mut result = Self::new();
+        for element in iterable {
+            if let Err(e) = result.push(element) {
+                return Err(CapacityError::new((result, e.element())));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Collects at most `N` items of `iterable` into a new vector. The
+    /// iterator is not advanced beyond the last item that fits.
+    pub fn from_iter_truncated<I>(iterable: I) -> Self where I: IntoIterator<Item = T> {
+        let mut result = Self::new();
+        for element in iterable.into_iter().take(N) {
+            unsafe { result.push_unchecked(element) };
+        }
+        result
+    }
+
+    /// Returns a raw pointer to the underlying array, valid for reads of
+    /// `len()` elements.
+    ///
+    /// The pointer may be used to compute addresses up to `capacity()`, but
+    /// reading the uninitialized elements beyond `len()` is undefined
+    /// behaviour.
+    pub const fn as_ptr(&self) -> *const T {
+        self.array.as_ptr() as *const T
+    }
+
+    /// Returns a raw mutable pointer to the underlying array, valid for reads
+    /// of `len()` elements and for writes of `capacity()` elements.
+    ///
+    /// Elements written beyond `len()` become part of the vector only after a
+    /// call to `set_len`.
+    pub const fn as_mut_ptr(&mut self) -> *mut T {
+        self.array.as_mut_ptr() as *mut T
+    }
+
+    /// Returns the unused part of the underlying array as a slice of
+    /// `MaybeUninit<T>`.
+    ///
+    /// Elements written to the returned slice become part of the vector only
+    /// after a call to `set_len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_vec::*;
+    /// let mut buffer: ArrayVec<u8, 16> = array_vec![1, 2];
+    /// let spare = buffer.spare_capacity_mut();
+    /// assert_eq!(14, spare.len());
+    /// spare[0].write(3);
+    /// spare[1].write(4);
+    /// unsafe { buffer.set_len(4) };
+    /// assert_eq!(buffer, [1, 2, 3, 4]);
+    /// ```
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        let len = self.len();
+        &mut self.array[len..]
+    }
+
+    /// Returns the contents of the vector together with its unused part, for
+    /// filling the spare capacity based on the existing elements.
+    pub fn split_at_spare_mut(&mut self) -> (&mut [T], &mut [MaybeUninit<T>]) {
+        let len = self.len();
+        let (init, spare) = self.array.split_at_mut(len);
+        let init = unsafe { slice::from_raw_parts_mut(init.as_mut_ptr() as *mut T, len) };
+        (init, spare)
+    }
+
+    /// Sets the length of the vector without dropping or initializing any
+    /// elements.
+    ///
+    /// This is meant for filling the vector through `as_mut_ptr` or
+    /// `spare_capacity_mut`, for example from C code or a DMA transfer.
+    ///
+    /// # Safety
+    ///
+    /// `len` must not exceed `capacity()`, and the first `len` elements must
+    /// be initialized. Elements beyond the new length are not dropped, so
+    /// shrinking the vector this way leaks them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_vec::*;
+    /// # unsafe fn read_bytes(dst: *mut u8, max: usize) -> usize {
+    /// #     let n = max.min(5);
+    /// #     core::ptr::write_bytes(dst, 0xAB, n);
+    /// #     n
+    /// # }
+    /// let mut buffer: ArrayVec<u8, 64> = ArrayVec::new();
+    /// unsafe {
+    ///     let read = read_bytes(buffer.as_mut_ptr(), buffer.capacity());
+    ///     buffer.set_len(read);
+    /// }
+    /// assert_eq!(buffer, [0xAB; 5]);
+    /// ```
+    pub const unsafe fn set_len(&mut self, len: usize) {
+        self.store_len(len);
     }
 
     /// Returns the maximal amount of elements that can be stored in this
     /// vector.
-    pub fn capacity(&self) -> usize {
-        mem::size_of::<A>() / mem::size_of::<T>()
+    pub const fn capacity(&self) -> usize {
+        N
     }
 
     /// Returns the number of elements currently stored in this vector.
-    pub fn length(&self) -> usize { self.idx }
+    pub const fn length(&self) -> usize { self.len() }
+
+    /// Returns `true` if the vector contains no elements.
+    pub const fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Returns `true` if no more elements can be added to the vector.
+    pub const fn is_full(&self) -> bool { self.len() == N }
 
     /// Attempts to add an element to the end of this collection. Returns `Err`
-    /// if there is no space left in the underlying array.
-    pub fn push(&mut self, x: T) -> Result<(), &'static str> {
-        if self.idx < self.capacity() {
-            unsafe {
-                let ptr = self.base_ptr_mut();
-                let mut cell = x;
-                mem::swap(&mut *ptr.offset(self.idx as isize), &mut cell);
-                mem::forget(cell);
-                self.idx = self.idx + 1;
-            }
+    /// if there is no space left in the underlying array; the error hands `x`
+    /// back to the caller.
+    pub const fn push(&mut self, x: T) -> Result<(), CapacityError<T>> {
+        if self.len() < self.capacity() {
+            unsafe { self.push_unchecked(x) };
             Ok(())
         } else {
-            Err("cannot push: this ArrayVec is full")
+            Err(CapacityError::new(x))
         }
     }
 
+    /// Appends an element, handling a full vector according to the overflow
+    /// policy `P`. See the `policy` module for the available policies, and
+    /// `PolicyVec` for a vector whose `push` always applies one policy.
+    ///
+    /// ```
+    /// use array_vec::*;
+    /// use array_vec::policy::{DropNewest, EvictOldest};
+    /// let mut a: ArrayVec<i32, 2> = array_vec![1, 2];
+    /// a.push_or::<DropNewest>(3);
+    /// assert_eq!(a, [1, 2]);
+    /// assert_eq!(Some(1), a.push_or::<EvictOldest>(3));
+    /// assert_eq!(a, [2, 3]);
+    /// ```
+    pub fn push_or<P: OverflowPolicy>(&mut self, x: T) -> P::Output<T> {
+        P::push(self, x)
+    }
+
+    /// Appends the element returned by `f` and returns a reference to it.
+    ///
+    /// `f` is only called once the capacity has been checked. Its result is
+    /// still returned by value and may be staged on the stack before it is
+    /// moved into the vector; use `emplace_uninit` or `emplace_with` to build
+    /// a large element directly in its slot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is already full. See `try_push_with` for a
+    /// non-panicking alternative.
+    pub fn push_with<F: FnOnce() -> T>(&mut self, f: F) -> &mut T {
+        match self.try_push_with(f) {
+            Ok(x) => x,
+            Err(_) => panic!("push_with: this ArrayVec is full (capacity is {})", N),
+        }
+    }
+
+    /// Appends the element returned by `f` and returns a reference to it.
+    /// Returns `Err` holding `f`, which is not called, if there is no space
+    /// left in the underlying array.
+    pub fn try_push_with<F: FnOnce() -> T>(&mut self, f: F)
+        -> Result<&mut T, CapacityError<F>>
+    {
+        if self.is_full() {
+            return Err(CapacityError::new(f));
+        }
+        let len = self.len();
+        self.array[len].write(f());
+        self.store_len(len + 1);
+        Ok(unsafe { self.array[len].assume_init_mut() })
+    }
+
+    /// Returns the free slot after the last element, or `None` if the vector
+    /// is full.
+    ///
+    /// The slot is part of the underlying array, so a large element can be
+    /// built in it without a copy on the stack. Once the element has been
+    /// written, `set_len(len() + 1)` makes it part of the vector; until then
+    /// the vector is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_vec::*;
+    /// let mut records: ArrayVec<[u32; 1024], 4> = ArrayVec::new();
+    /// let len = records.len();
+    /// let slot = records.emplace_uninit().unwrap();
+    /// unsafe {
+    ///     slot.as_mut_ptr().write_bytes(0, 1);
+    ///     records.set_len(len + 1);
+    /// }
+    /// assert_eq!(1, records.len());
+    /// assert_eq!(0, records[0][3]);
+    /// ```
+    pub fn emplace_uninit(&mut self) -> Option<&mut MaybeUninit<T>> {
+        self.spare_capacity_mut().first_mut()
+    }
+
+    /// Appends an element that `init` initializes directly in the free slot
+    /// of the underlying array, and returns a reference to it. This combines
+    /// `emplace_uninit` and `set_len`.
+    ///
+    /// The element is counted only after `init` returns, so if `init` panics
+    /// the vector is left unchanged.
+    ///
+    /// # Safety
+    ///
+    /// `init` must fully initialize the `MaybeUninit<T>` it is given.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is already full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_vec::*;
+    /// let mut records: ArrayVec<[u32; 1024], 4> = ArrayVec::new();
+    /// let record = unsafe {
+    ///     records.emplace_with(|slot| slot.as_mut_ptr().write_bytes(0, 1))
+    /// };
+    /// record[3] = 7;
+    /// assert_eq!(7, records[0][3]);
+    /// ```
+    pub unsafe fn emplace_with<F>(&mut self, init: F) -> &mut T
+        where F: FnOnce(&mut MaybeUninit<T>)
+    {
+        let len = self.len();
+        assert!(len < N, "emplace_with: this ArrayVec is full (capacity is {})", N);
+        init(&mut self.array[len]);
+        self.store_len(len + 1);
+        self.array[len].assume_init_mut()
+    }
+
+    /// Appends `x` without checking the capacity. The caller must ensure that
+    /// the vector is not full.
+    const unsafe fn push_unchecked(&mut self, x: T) {
+        debug_assert!(self.len() < N);
+        // The slot is uninitialized, so nothing is dropped here.
+        ptr::write(self.as_mut_ptr().add(self.len()), x);
+        self.store_len(self.len() + 1);
+    }
+
     /// Attempts remove the last element of this collection. Returns `None` if
     /// there are no elements present.
-    pub fn pop(&mut self) -> Option<T> {
-        if self.idx <= 0 {
+    pub const fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
             None
         } else {
+            // The length is decremented first, so the slot is considered
+            // uninitialized once its value has been moved out.
+            let len = self.len() - 1;
+            self.store_len(len);
+            unsafe { Some(ptr::read(self.as_ptr().add(len))) }
+        }
+    }
+
+    /// Inserts an element at position `index`, shifting all elements after it
+    /// to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len` or if the vector is already full. See
+    /// `try_insert` for a non-panicking alternative.
+    pub fn insert(&mut self, index: usize, x: T) {
+        if self.try_insert(index, x).is_err() {
+            panic!("insert: this ArrayVec is full (capacity is {})", N);
+        }
+    }
+
+    /// Attempts to insert an element at position `index`, shifting all
+    /// elements after it to the right. Returns `Err` holding `x` if there is
+    /// no space left in the underlying array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn try_insert(&mut self, index: usize, x: T) -> Result<(), CapacityError<T>> {
+        let len = self.len();
+        assert!(index <= len, "insertion index (is {}) should be <= len (is {})",
+                index, len);
+        if len == N {
+            return Err(CapacityError::new(x));
+        }
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            ptr::copy(p, p.add(1), len - index);
+            ptr::write(p, x);
+        }
+        self.store_len(len + 1);
+        Ok(())
+    }
+
+    /// Removes and returns the element at position `index`, shifting all
+    /// elements after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        match self.try_remove(index) {
+            Some(x) => x,
+            None => panic!("removal index (is {}) should be < len (is {})", index, len),
+        }
+    }
+
+    /// Removes and returns the element at position `index`, shifting all
+    /// elements after it to the left. Returns `None` if `index` is out of
+    /// bounds.
+    pub fn try_remove(&mut self, index: usize) -> Option<T> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            let x = ptr::read(p);
+            ptr::copy(p.add(1), p, len - index - 1);
+            self.store_len(len - 1);
+            Some(x)
+        }
+    }
+
+    /// Removes and returns the element at position `index`, replacing it with
+    /// the last element. This does not preserve ordering but is O(1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        match self.try_swap_remove(index) {
+            Some(x) => x,
+            None => panic!("swap_remove index (is {}) should be < len (is {})", index, len),
+        }
+    }
+
+    /// Removes and returns the element at position `index`, replacing it with
+    /// the last element. Returns `None` if `index` is out of bounds.
+    pub fn try_swap_remove(&mut self, index: usize) -> Option<T> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        unsafe {
+            let base = self.as_mut_ptr();
+            let x = ptr::read(base.add(index));
+            // Moves the last element into the hole; a no-op copy if `index`
+            // already is the last position.
+            ptr::copy(base.add(len - 1), base.add(index), 1);
+            self.store_len(len - 1);
+            Some(x)
+        }
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest. Has no effect
+    /// if the vector is already shorter than that.
+    ///
+    /// If `T` has no destructor this only updates the length.
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
+        }
+        // The length is updated first, so the vector never observes dropped
+        // slots. If a destructor panics, `drop_in_place` still drops the rest
+        // of the slice while unwinding.
+        self.store_len(len);
+        unsafe {
+            let tail = ptr::slice_from_raw_parts_mut(self.as_mut_ptr().add(len),
+                                                     old_len - len);
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Removes all elements from the vector.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Drops the vector, destroying its elements from last to first.
+    ///
+    /// Dropping an `ArrayVec` normally destroys its elements from first to
+    /// last, the same order as `Vec`. This is useful when later elements
+    /// depend on earlier ones, for example in a stack of guards. If a
+    /// destructor panics, the remaining elements are still dropped in reverse
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use array_vec::*;
+    /// use std::cell::RefCell;
+    ///
+    /// struct Guard<'a>(i32, &'a RefCell<Vec<i32>>);
+    ///
+    /// impl Drop for Guard<'_> {
+    ///     fn drop(&mut self) { self.1.borrow_mut().push(self.0) }
+    /// }
+    ///
+    /// let log = RefCell::new(Vec::new());
+    /// let guards = array_vec![Guard(1, &log), Guard(2, &log), Guard(3, &log); 4];
+    /// guards.drop_in_reverse();
+    /// assert_eq!(*log.borrow(), [3, 2, 1]);
+    /// ```
+    pub fn drop_in_reverse(mut self) {
+        struct PopRest<'a, T, const N: usize, L: LenUint>(&'a mut ArrayVec<T, N, L>);
+
+        impl<T, const N: usize, L: LenUint> Drop for PopRest<'_, T, N, L> {
+            fn drop(&mut self) {
+                while self.0.pop().is_some() {}
+            }
+        }
+
+        let guard = PopRest(&mut self);
+        while guard.0.pop().is_some() {}
+        mem::forget(guard);
+    }
+
+    /// Resizes the vector to `new_len` elements, either by truncating it or by
+    /// appending clones of `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` exceeds the capacity. See `try_resize` for a
+    /// non-panicking alternative.
+    pub fn resize(&mut self, new_len: usize, value: T) where T: Clone {
+        if self.try_resize(new_len, value).is_err() {
+            panic!("resize: new length (is {}) exceeds capacity (is {})", new_len, N);
+        }
+    }
+
+    /// Resizes the vector to `new_len` elements, either by truncating it or by
+    /// appending clones of `value`. Returns `Err` holding `value` and leaves
+    /// the vector unchanged if `new_len` exceeds the capacity.
+    pub fn try_resize(&mut self, new_len: usize, value: T)
+        -> Result<(), CapacityError<T>> where T: Clone
+    {
+        if new_len > N {
+            return Err(CapacityError::new(value));
+        }
+        if new_len <= self.len() {
+            self.truncate(new_len);
+        } else {
+            while self.len() + 1 < new_len {
+                unsafe { self.push_unchecked(value.clone()) };
+            }
+            unsafe { self.push_unchecked(value) };
+        }
+        Ok(())
+    }
+
+    /// Resizes the vector to `new_len` elements, either by truncating it or by
+    /// appending values returned by `f`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` exceeds the capacity.
+    pub fn resize_with<F: FnMut() -> T>(&mut self, new_len: usize, mut f: F) {
+        assert!(new_len <= N, "resize_with: new length (is {}) exceeds capacity (is {})",
+                new_len, N);
+        if new_len <= self.len() {
+            self.truncate(new_len);
+        } else {
+            while self.len() < new_len {
+                unsafe { self.push_unchecked(f()) };
+            }
+        }
+    }
+
+    /// Attempts to append all items of `iterable`, leaving the vector
+    /// unchanged if they do not all fit.
+    ///
+    /// On overflow the vector is restored to its original length and the
+    /// error holds the items that had already been appended, in order, along
+    /// with an iterator over the rest: the item that did not fit followed by
+    /// the unconsumed remainder of `iterable`.
+    ///
+    /// ```
+    /// use array_vec::*;
+    /// let mut a: ArrayVec<i32, 3> = array_vec![0];
+    /// let (consumed, rest) = a.try_extend_all(1..5).unwrap_err().element();
+    /// assert_eq!(&[0], &*a);
+    /// assert_eq!(&[1, 2], &*consumed);
+    /// assert!(rest.eq(3..5));
+    /// ```
+    pub fn try_extend_all<I>(&mut self, iterable: I) -> Result<(), Rejected<T, I::IntoIter, N, L>>
+        where I: IntoIterator<Item = T>
+    {
+        let original_len = self.len();
+        let mut iter = iterable.into_iter();
+        for element in iter.by_ref() {
+            if let Err(e) = self.push(element) {
+                let consumed = self.split_off(original_len);
+                let rest = iter::once(e.element()).chain(iter);
+                return Err(CapacityError::new((consumed, rest)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends clones of all elements of `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the elements do not fit. See `try_extend_from_slice` for a
+    /// non-panicking alternative.
+    pub fn extend_from_slice(&mut self, other: &[T]) where T: Clone {
+        if self.try_extend_from_slice(other).is_err() {
+            panic!("extend_from_slice: {} more elements do not fit (capacity is {}, \
+                    length is {})", other.len(), N, self.len());
+        }
+    }
+
+    /// Attempts to append clones of all elements of `other`. Returns `Err`
+    /// and leaves the vector unchanged if they do not all fit.
+    ///
+    /// For `T: Copy` this compiles down to a single `memcpy`.
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError>
+        where T: Clone
+    {
+        if other.len() > N - self.len() {
+            return Err(CapacityError::new(()));
+        }
+        // The length is kept in a local and stored once, so that the loop
+        // reduces to a `memcpy` for `Copy` types.
+        let mut guard = SetLenOnDrop::new(self);
+        let base = guard.vec.as_mut_ptr();
+        for x in other {
+            unsafe { ptr::write(base.add(guard.len), x.clone()) };
+            guard.len += 1;
+        }
+        Ok(())
+    }
+
+    /// Appends clones of the elements in `src` to the end of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` is out of bounds or if the elements do not fit. See
+    /// `try_extend_from_within` for an alternative that does not panic on
+    /// overflow.
+    pub fn extend_from_within<R>(&mut self, src: R)
+        where T: Clone, R: ops::RangeBounds<usize>
+    {
+        if self.try_extend_from_within(src).is_err() {
+            panic!("extend_from_within: elements do not fit (capacity is {})", N);
+        }
+    }
+
+    /// Attempts to append clones of the elements in `src` to the end of the
+    /// vector. Returns `Err` and leaves the vector unchanged if they do not
+    /// all fit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` is out of bounds.
+    pub fn try_extend_from_within<R>(&mut self, src: R) -> Result<(), CapacityError>
+        where T: Clone, R: ops::RangeBounds<usize>
+    {
+        let range = check_range(src, self.len());
+        if range.len() > N - self.len() {
+            return Err(CapacityError::new(()));
+        }
+        for i in range {
+            // The length is updated after every element, so a panicking
+            // `clone` leaves only initialized elements behind. `store_len`
+            // reborrows the vector, so the pointer is derived anew each time.
+            let len = self.len();
+            unsafe {
+                let base = self.as_mut_ptr();
+                ptr::write(base.add(len), (*base.add(i)).clone());
+            }
+            self.store_len(len + 1);
+        }
+        Ok(())
+    }
+
+    /// Moves all elements of `other` to the end of this vector, leaving
+    /// `other` empty. `other` may have a different capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the elements do not fit. See `try_append` for a non-panicking
+    /// alternative.
+    pub fn append<const M: usize, L2: LenUint>(&mut self, other: &mut ArrayVec<T, M, L2>) {
+        if self.try_append(other).is_err() {
+            panic!("append: {} more elements do not fit (capacity is {}, length is {})",
+                   other.length(), N, self.len());
+        }
+    }
+
+    /// Attempts to move all elements of `other` to the end of this vector.
+    /// Returns `Err` and leaves both vectors unchanged if they do not all
+    /// fit.
+    pub fn try_append<const M: usize, L2: LenUint>(&mut self, other: &mut ArrayVec<T, M, L2>)
+        -> Result<(), CapacityError>
+    {
+        let count = other.len();
+        if count > N - self.len() {
+            return Err(CapacityError::new(()));
+        }
+        unsafe {
+            let dst = self.as_mut_ptr().add(self.len());
+            ptr::copy_nonoverlapping(other.as_ptr(), dst, count);
+        }
+        other.store_len(0);
+        self.store_len(self.len() + count);
+        Ok(())
+    }
+
+    /// Removes the elements in `range` from the vector and returns them as an
+    /// iterator. The elements after the range are shifted back once the
+    /// iterator is dropped; elements that were not yielded are dropped with
+    /// it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn drain<R: ops::RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, N, L> {
+        let range = check_range(range, self.len());
+        Drain::new(self, range.start, range.end)
+    }
+
+    /// Replaces the elements in `range` with the items of `replace_with` and
+    /// returns the removed elements as an iterator.
+    ///
+    /// The replacement happens when the returned iterator is dropped; removed
+    /// elements that were not yielded are dropped with it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds. Dropping the returned iterator
+    /// panics if the replacement does not fit; the vector then holds as many
+    /// replacement items as did fit. See `try_splice` for an alternative that
+    /// checks the capacity up front.
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter, N, L>
+        where R: ops::RangeBounds<usize>, I: IntoIterator<Item = T>
+    {
+        Splice { drain: self.drain(range), replace_with: replace_with.into_iter() }
+    }
+
+    /// Attempts to replace the elements in `range` with the items of
+    /// `replace_with`, dropping the removed elements.
+    ///
+    /// If the result would exceed the capacity, returns `Err` holding the
+    /// first item that did not fit and leaves the vector unchanged. The items
+    /// consumed before it are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn try_splice<R, I>(&mut self, range: R, replace_with: I)
+        -> Result<(), CapacityError<T>>
+        where R: ops::RangeBounds<usize>, I: IntoIterator<Item = T>
+    {
+        let range = check_range(range, self.len());
+        let room = N - self.len() + range.len();
+        let mut iter = replace_with.into_iter();
+        let mut buffer = Self::new();
+        for x in iter.by_ref().take(room) {
+            unsafe { buffer.push_unchecked(x) };
+        }
+        if let Some(x) = iter.next() {
+            return Err(CapacityError::new(x));
+        }
+        self.splice(range, buffer);
+        Ok(())
+    }
+
+    /// Retains only the elements for which `f` returns `true`, preserving
+    /// their order. Elements are visited exactly once, front to back.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|x| f(x));
+    }
+
+    /// Like `retain`, but passes a mutable reference to each element.
+    ///
+    /// If `f` or an element's destructor panics, the elements that were not
+    /// visited yet are kept.
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        // Closes the gap left by removed elements, even when unwinding.
+        struct Guard<'a, T, const N: usize, L: LenUint> {
+            vec: &'a mut ArrayVec<T, N, L>,
+            processed: usize,
+            deleted: usize,
+            original_len: usize,
+        }
+
+        impl<T, const N: usize, L: LenUint> Drop for Guard<'_, T, N, L> {
+            fn drop(&mut self) {
+                if self.deleted > 0 {
+                    unsafe {
+                        let base = self.vec.as_mut_ptr();
+                        ptr::copy(base.add(self.processed),
+                                  base.add(self.processed - self.deleted),
+                                  self.original_len - self.processed);
+                    }
+                }
+                self.vec.store_len(self.original_len - self.deleted);
+            }
+        }
+
+        let original_len = self.len();
+        self.store_len(0);
+        let mut g = Guard { vec: self, processed: 0, deleted: 0, original_len };
+        let base = g.vec.as_mut_ptr();
+        while g.processed < original_len {
+            let cur = unsafe { &mut *base.add(g.processed) };
+            if !f(cur) {
+                g.processed += 1;
+                g.deleted += 1;
+                unsafe { ptr::drop_in_place(cur) };
+            } else {
+                if g.deleted > 0 {
+                    unsafe {
+                        ptr::copy_nonoverlapping(cur, base.add(g.processed - g.deleted), 1);
+                    }
+                }
+                g.processed += 1;
+            }
+        }
+    }
+
+    /// Removes consecutive repeated elements, keeping the first of each run.
+    pub fn dedup(&mut self) where T: PartialEq {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Removes consecutive elements that map to the same key, keeping the
+    /// first of each run.
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Removes consecutive elements for which `same_bucket` returns `true`.
+    ///
+    /// `same_bucket(a, b)` is passed the element `a` under consideration and
+    /// the last retained element `b`; if it returns `true`, `a` is removed.
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        // Moves the unvisited elements next to the retained ones, even when
+        // unwinding.
+        struct Guard<'a, T, const N: usize, L: LenUint> {
+            vec: &'a mut ArrayVec<T, N, L>,
+            read: usize,
+            write: usize,
+            original_len: usize,
+        }
+
+        impl<T, const N: usize, L: LenUint> Drop for Guard<'_, T, N, L> {
+            fn drop(&mut self) {
+                unsafe {
+                    let base = self.vec.as_mut_ptr();
+                    ptr::copy(base.add(self.read), base.add(self.write),
+                              self.original_len - self.read);
+                }
+                self.vec.store_len(self.write + self.original_len - self.read);
+            }
+        }
+
+        let original_len = self.len();
+        if original_len <= 1 {
+            return;
+        }
+        self.store_len(0);
+        let mut g = Guard { vec: self, read: 1, write: 1, original_len };
+        let base = g.vec.as_mut_ptr();
+        while g.read < original_len {
             unsafe {
-                let ptr = self.base_ptr_mut();
-                let mut cell = mem::uninitialized();
-                mem::swap(&mut *ptr.offset(self.idx as isize - 1), &mut cell);
-                self.idx = self.idx - 1;
-                Some(cell)
+                let cur = &mut *base.add(g.read);
+                let prev = &mut *base.add(g.write - 1);
+                if same_bucket(cur, prev) {
+                    g.read += 1;
+                    ptr::drop_in_place(cur);
+                } else {
+                    if g.read != g.write {
+                        ptr::copy_nonoverlapping(cur, base.add(g.write), 1);
+                    }
+                    g.write += 1;
+                    g.read += 1;
+                }
             }
         }
     }
+
+    /// Returns an iterator that removes and yields the elements in `range`
+    /// for which `filter` returns `true`. The remaining elements keep their
+    /// order.
+    ///
+    /// The removal happens lazily: elements that the iterator has not reached
+    /// when it is dropped are kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn extract_if<F, R>(&mut self, range: R, filter: F) -> ExtractIf<'_, T, F, N, L>
+        where F: FnMut(&mut T) -> bool, R: ops::RangeBounds<usize>
+    {
+        let range = check_range(range, self.len());
+        ExtractIf::new(self, range.start, range.end, filter)
+    }
+
+    /// Splits the vector in two at `at`. The elements `[at, len)` are moved
+    /// into the returned vector, while `self` keeps `[0, at)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(at <= len, "split_off index (is {}) should be <= len (is {})", at, len);
+        let mut other = Self::new();
+        unsafe {
+            ptr::copy_nonoverlapping(self.as_ptr().add(at), other.as_mut_ptr(), len - at);
+        }
+        self.store_len(at);
+        other.store_len(len - at);
+        other
+    }
+}
+
+/// Tracks the length of a vector that is being filled through a raw pointer,
+/// and stores it when dropped, even if a `clone` panics halfway through.
+struct SetLenOnDrop<'a, T, const N: usize, L: LenUint> {
+    vec: &'a mut ArrayVec<T, N, L>,
+    len: usize,
 }
 
-impl<T, A: FixedSizeArray<T>> ops::Drop for ArrayVec<T, A> {
+impl<'a, T, const N: usize, L: LenUint> SetLenOnDrop<'a, T, N, L> {
+    fn new(vec: &'a mut ArrayVec<T, N, L>) -> Self {
+        let len = vec.len();
+        SetLenOnDrop { vec, len }
+    }
+}
+
+impl<T, const N: usize, L: LenUint> Drop for SetLenOnDrop<'_, T, N, L> {
     fn drop(&mut self) {
-        while self.length() > 0 {
-            self.pop();
-            // The popped element goes out of scope here and its destructor is
-            // run (if present).
-        }
+        self.vec.store_len(self.len);
+    }
+}
+
+/// Converts `range` to a `Range` within `[0, len]`, panicking if it is out of
+/// bounds.
+fn check_range<R: ops::RangeBounds<usize>>(range: R, len: usize) -> ops::Range<usize> {
+    use core::ops::Bound::*;
+    let start = match range.start_bound() {
+        Included(&n) => n,
+        Excluded(&n) => n.checked_add(1).expect("range start overflows usize"),
+        Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Included(&n) => n.checked_add(1).expect("range end overflows usize"),
+        Excluded(&n) => n,
+        Unbounded => len,
+    };
+    assert!(start <= end, "range start (is {}) should be <= range end (is {})", start, end);
+    assert!(end <= len, "range end (is {}) should be <= len (is {})", end, len);
+    start..end
+}
 
-        // The array now contains garbage and we have to prevent its destructor
-        // from running but we cannot mem::forget() out of borrowed context. To
-        // work around this, self.array is an Option type and we swap it with
-        // None.
-        let mut to_be_forgotten: Option<A> = None;
-        mem::swap(&mut self.array, &mut to_be_forgotten);
-        unsafe { mem::forget(to_be_forgotten) };
+impl<T, const N: usize, L: LenUint> ops::Drop for ArrayVec<T, N, L> {
+    fn drop(&mut self) {
+        // Drops the elements front to back, like `Vec` and arrays do. If a
+        // destructor panics, the rest are still dropped while unwinding.
+        self.clear();
+
+        // The remaining slots are `MaybeUninit` and have no destructors, so
+        // there is nothing else to do.
     }
 }
 
-impl<T, A: FixedSizeArray<T>> FromIterator<T> for ArrayVec<T, A> {
-    fn from_iter<I: IntoIterator<Item=T>>(iterable: I) -> ArrayVec<T, A> {
+/// Collects an iterator into an `ArrayVec`.
+///
+/// # Panics
+///
+/// Panics if the iterator yields more elements than fit. Use
+/// `ArrayVec::try_from_iter`, `ArrayVec::from_iter_truncated` or the
+/// `CollectArrayVec` iterator extension in code that must not panic.
+impl<T, const N: usize, L: LenUint> FromIterator<T> for ArrayVec<T, N, L> {
+    fn from_iter<I: IntoIterator<Item=T>>(iterable: I) -> ArrayVec<T, N, L> {
         let mut result = ArrayVec::new();
         for element in iterable {
             result.push(element).unwrap();
@@ -134,35 +1125,153 @@ impl<T, A: FixedSizeArray<T>> FromIterator<T> for ArrayVec<T, A> {
     }
 }
 
-impl<T, A: FixedSizeArray<T>> ops::Index<usize> for ArrayVec<T, A> {
+/// Appends the items of an iterator.
+///
+/// # Panics
+///
+/// Panics if the iterator yields more elements than fit; the elements
+/// appended before the overflow are kept. Use `TryExtend` or
+/// `ArrayVec::try_extend_all` in code that must not panic.
+impl<T, const N: usize, L: LenUint> Extend<T> for ArrayVec<T, N, L> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iterable: I) {
+        for element in iterable {
+            if self.push(element).is_err() {
+                panic!("extend: iterator yields more elements than fit (capacity is {})", N);
+            }
+        }
+    }
+}
+
+/// Appends copies of the items of an iterator.
+///
+/// # Panics
+///
+/// Panics on overflow, like `Extend<T>`.
+impl<'a, T: Copy + 'a, const N: usize, L: LenUint> Extend<&'a T> for ArrayVec<T, N, L> {
+    fn extend<I: IntoIterator<Item=&'a T>>(&mut self, iterable: I) {
+        self.extend(iterable.into_iter().copied());
+    }
+}
+
+impl<T, const N: usize, L: LenUint> Default for ArrayVec<T, N, L> {
+    /// Creates an empty `ArrayVec`.
+    fn default() -> Self {
+        ArrayVec::new()
+    }
+}
+
+impl<T, const N: usize, L: LenUint> ops::Index<usize> for ArrayVec<T, N, L> {
     type Output = T;
 
-    fn index<'a>(&'a self, index: usize) -> &'a T {
+    fn index(&self, index: usize) -> &T {
         &(**self)[index]
     }
 }
 
-impl<T, A: FixedSizeArray<T>> ops::Deref for ArrayVec<T, A> {
+impl<T, const N: usize, L: LenUint> ops::Deref for ArrayVec<T, N, L> {
     type Target = [T];
 
     fn deref(&self) -> &[T] {
         unsafe {
-            slice::from_raw_parts(self.base_ptr(), self.length())
+            slice::from_raw_parts(self.as_ptr(), self.length())
         }
     }
 }
 
-impl<T, A: FixedSizeArray<T>> ops::DerefMut for ArrayVec<T, A> {
+impl<T, const N: usize, L: LenUint> ops::DerefMut for ArrayVec<T, N, L> {
     fn deref_mut(&mut self) -> &mut [T] {
         unsafe {
-            slice::from_raw_parts_mut(self.base_ptr_mut(), self.length())
+            slice::from_raw_parts_mut(self.as_mut_ptr(), self.length())
+        }
+    }
+}
+
+/// For `T: Copy`, cloning compiles down to a single `memcpy`. `clone` returns
+/// the new vector by value, which may cost another copy of the whole array;
+/// `clone_from` writes into an existing vector instead.
+impl<T: Clone, const N: usize, L: LenUint> Clone for ArrayVec<T, N, L> {
+    fn clone(&self) -> Self {
+        let mut result = Self::new();
+        // Cannot fail, as both vectors have the same capacity.
+        let _ = result.try_extend_from_slice(self);
+        result
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        // Reuses the existing elements and only clones or drops the rest.
+        self.truncate(source.len());
+        let (init, tail) = source.split_at(self.len());
+        self.as_mut().clone_from_slice(init);
+        let _ = self.try_extend_from_slice(tail);
+    }
+}
+
+impl<T, const N: usize, L: LenUint> From<[T; N]> for ArrayVec<T, N, L> {
+    fn from(array: [T; N]) -> Self {
+        ArrayVec::from_array(array)
+    }
+}
+
+/// Clones the elements of a slice. Fails if the slice is longer than the
+/// capacity.
+impl<T: Clone, const N: usize, L: LenUint> TryFrom<&[T]> for ArrayVec<T, N, L> {
+    type Error = CapacityError;
+
+    fn try_from(slice: &[T]) -> Result<Self, CapacityError> {
+        let mut result = Self::new();
+        result.try_extend_from_slice(slice)?;
+        Ok(result)
+    }
+}
+
+/// Moves the elements out of a `Vec`. Fails if the `Vec` is longer than the
+/// capacity, handing it back unchanged.
+#[cfg(feature = "alloc")]
+impl<T, const N: usize, L: LenUint> TryFrom<Vec<T>> for ArrayVec<T, N, L> {
+    type Error = CapacityError<Vec<T>>;
+
+    fn try_from(mut vec: Vec<T>) -> Result<Self, CapacityError<Vec<T>>> {
+        let len = vec.len();
+        if len > N {
+            return Err(CapacityError::new(vec));
+        }
+        let mut result = Self::new();
+        unsafe {
+            vec.set_len(0);
+            ptr::copy_nonoverlapping(vec.as_ptr(), result.as_mut_ptr(), len);
         }
+        result.store_len(len);
+        Ok(result)
+    }
+}
+
+impl<T, const N: usize, L: LenUint> AsRef<[T]> for ArrayVec<T, N, L> {
+    fn as_ref(&self) -> &[T] {
+        self
     }
 }
 
-impl<T: Debug, A: FixedSizeArray<T>> Debug for ArrayVec<T, A> {
+impl<T, const N: usize, L: LenUint> AsMut<[T]> for ArrayVec<T, N, L> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize, L: LenUint> Borrow<[T]> for ArrayVec<T, N, L> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const N: usize, L: LenUint> BorrowMut<[T]> for ArrayVec<T, N, L> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T: Debug, const N: usize, L: LenUint> Debug for ArrayVec<T, N, L> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-        let as_slice: &[T] = &**self;
+        let as_slice: &[T] = self;
         Debug::fmt(as_slice, f)
     }
 }
@@ -170,14 +1279,13 @@ impl<T: Debug, A: FixedSizeArray<T>> Debug for ArrayVec<T, A> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use core::prelude::*;
 
     use core::ops;
     use core::mem;
 
     #[test]
     fn push_pop() {
-        let mut a: ArrayVec<i32, [_; 10]> = ArrayVec::new();
+        let mut a: ArrayVec<i32, 10> = ArrayVec::new();
         assert_eq!(0, a.length());
         assert_eq!(10, a.capacity());
         a.push(5).unwrap();
@@ -188,22 +1296,539 @@ mod test {
 
     #[test]
     fn failures() {
-        let mut a: ArrayVec<i32, [_; 1]> = ArrayVec::new();
+        let mut a: ArrayVec<i32, 1> = ArrayVec::new();
         assert_eq!(0, a.length());
         assert_eq!(None, a.pop());
         assert_eq!(0, a.length());
         assert_eq!(Ok(()), a.push(7));
         assert_eq!(1, a.length());
-        assert!(a.push(13).is_err());
+        assert_eq!(Err(CapacityError::new(13)), a.push(13));
         assert_eq!(1, a.length());
     }
 
     #[test]
     fn zero_len() {
-        let mut useless: ArrayVec<i32, [_; 0]> = ArrayVec::new();
+        let mut useless: ArrayVec<i32, 0> = ArrayVec::new();
         assert_eq!(0, useless.length());
         assert_eq!(0, useless.capacity());
-        assert!(useless.push(7).is_err());
+        assert_eq!(7, useless.push(7).unwrap_err().element());
+    }
+
+    #[test]
+    fn zero_sized_types() {
+        use core::cell::Cell;
+        use core::marker::PhantomData;
+
+        std::thread_local!(static DROPPED: Cell<usize> = const { Cell::new(0) });
+
+        #[derive(Clone, Debug, PartialEq)]
+        struct Marker;
+
+        impl ops::Drop for Marker {
+            fn drop(&mut self) { DROPPED.with(|d| d.set(d.get() + 1)) }
+        }
+
+        let mut units: compat::ArrayVec<(), [(); 8]> = ArrayVec::new();
+        assert_eq!(8, units.capacity());
+        for _ in 0..8 {
+            units.push(()).unwrap();
+        }
+        assert!(units.push(()).is_err());
+        assert_eq!(8, units.iter().count());
+        assert_eq!(Some(()), units.pop());
+        assert_eq!(7, units.len());
+
+        let mut phantoms: ArrayVec<PhantomData<std::string::String>, 3> = ArrayVec::new();
+        phantoms.extend([PhantomData; 3]);
+        assert!(phantoms.is_full());
+        assert_eq!(3, phantoms.into_iter().rev().count());
+
+        let mut markers: ArrayVec<Marker, 6> = ArrayVec::new();
+        markers.resize(6, Marker);
+        assert_eq!(0, DROPPED.with(Cell::get));
+        markers.remove(2);
+        markers.drain(..2);
+        markers.retain(|_| false);
+        assert!(markers.is_empty());
+        assert_eq!(6, DROPPED.with(Cell::get));
+        markers.push(Marker).unwrap();
+        markers.insert(0, Marker);
+        let mut iter = markers.clone().into_iter();
+        assert_eq!(Some(Marker), iter.next());
+        drop(iter);
+        assert_eq!(2, markers.len());
+        drop(markers);
+        assert_eq!(11, DROPPED.with(Cell::get));
+    }
+
+    #[test]
+    fn invalid_bit_patterns() {
+        let mut bools: ArrayVec<bool, 4> = ArrayVec::new();
+        bools.push(true).unwrap();
+        bools.push(false).unwrap();
+        assert_eq!(Some(false), bools.pop());
+        assert_eq!(Some(true), bools.pop());
+        assert_eq!(None, bools.pop());
+
+        let (x, y) = (1, 2);
+        let mut refs: ArrayVec<&i32, 2> = ArrayVec::new();
+        refs.push(&x).unwrap();
+        refs.push(&y).unwrap();
+        assert_eq!(Some(&2), refs.pop());
+
+        let mut opts: ArrayVec<Option<core::cmp::Ordering>, 3> = ArrayVec::new();
+        opts.push(Some(core::cmp::Ordering::Less)).unwrap();
+        opts.push(None).unwrap();
+        assert_eq!(Some(None), opts.pop());
+        assert_eq!(Some(Some(core::cmp::Ordering::Less)), opts.pop());
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut a: ArrayVec<i32, 4> = ArrayVec::new();
+        a.insert(0, 2);
+        a.insert(0, 1);
+        a.insert(2, 4);
+        a.insert(2, 3);
+        assert_eq!(&[1, 2, 3, 4], &*a);
+        assert_eq!(Err(CapacityError::new(5)), a.try_insert(1, 5));
+        assert_eq!(2, a.remove(1));
+        assert_eq!(&[1, 3, 4], &*a);
+        assert_eq!(None, a.try_remove(3));
+        assert_eq!(1, a.swap_remove(0));
+        assert_eq!(&[4, 3], &*a);
+        assert_eq!(Some(3), a.try_swap_remove(1));
+        assert_eq!(None, a.try_swap_remove(1));
+        assert_eq!(&[4], &*a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        let mut a: ArrayVec<i32, 4> = ArrayVec::new();
+        a.insert(1, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_full() {
+        let mut a: ArrayVec<i32, 1> = ArrayVec::new();
+        a.insert(0, 0);
+        a.insert(0, 1);
+    }
+
+    #[test]
+    fn truncate_resize() {
+        let mut a: ArrayVec<i32, 5> = ArrayVec::new();
+        a.resize(3, 7);
+        assert_eq!(&[7, 7, 7], &*a);
+        a.truncate(5);
+        assert_eq!(3, a.length());
+        a.truncate(1);
+        assert_eq!(&[7], &*a);
+        let mut next = 0;
+        a.resize_with(4, || { next += 1; next });
+        assert_eq!(&[7, 1, 2, 3], &*a);
+        assert_eq!(Err(CapacityError::new(9)), a.try_resize(6, 9));
+        assert_eq!(&[7, 1, 2, 3], &*a);
+        assert_eq!(Ok(()), a.try_resize(2, 9));
+        assert_eq!(&[7, 1], &*a);
+        a.clear();
+        assert_eq!(0, a.length());
+    }
+
+    #[test]
+    fn truncate_drops() {
+        let rc = std::rc::Rc::new(());
+        let mut a: ArrayVec<std::rc::Rc<()>, 4> = ArrayVec::new();
+        a.resize(4, rc.clone());
+        assert_eq!(5, std::rc::Rc::strong_count(&rc));
+        a.truncate(1);
+        assert_eq!(2, std::rc::Rc::strong_count(&rc));
+        a.clear();
+        assert_eq!(1, std::rc::Rc::strong_count(&rc));
+    }
+
+    #[test]
+    #[should_panic]
+    fn resize_over_capacity() {
+        let mut a: ArrayVec<i32, 2> = ArrayVec::new();
+        a.resize(3, 0);
+    }
+
+    #[test]
+    fn extend_from_slices() {
+        let mut a: ArrayVec<i32, 6> = ArrayVec::new();
+        a.extend_from_slice(&[1, 2]);
+        assert_eq!(Ok(()), a.try_extend_from_slice(&[3]));
+        assert_eq!(Err(CapacityError::new(())), a.try_extend_from_slice(&[0; 4]));
+        assert_eq!(&[1, 2, 3], &*a);
+        a.extend_from_within(1..);
+        assert_eq!(&[1, 2, 3, 2, 3], &*a);
+        assert_eq!(Err(CapacityError::new(())), a.try_extend_from_within(..2));
+        a.extend_from_within(..=0);
+        assert_eq!(&[1, 2, 3, 2, 3, 1], &*a);
+    }
+
+    #[test]
+    fn extend_from_slice_panic_safety() {
+        struct Fragile(i32, std::rc::Rc<()>);
+
+        impl Clone for Fragile {
+            fn clone(&self) -> Self {
+                assert!(self.0 != 3);
+                Fragile(self.0, self.1.clone())
+            }
+        }
+
+        let rc = std::rc::Rc::new(());
+        let source: std::vec::Vec<Fragile> = (1..5).map(|i| Fragile(i, rc.clone())).collect();
+        let mut a: ArrayVec<Fragile, 8> = ArrayVec::new();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            a.extend_from_slice(&source);
+        }));
+        assert!(result.is_err());
+        assert_eq!(2, a.len());
+        assert_eq!(7, std::rc::Rc::strong_count(&rc));
+        a.clear();
+        assert_eq!(5, std::rc::Rc::strong_count(&rc));
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_from_within_out_of_bounds() {
+        let mut a = array_vec![1; 6];
+        a.extend_from_within(0..2);
+    }
+
+    #[test]
+    fn extend_all_or_nothing() {
+        let mut a: ArrayVec<std::string::String, 4> = array_vec!["x".into()];
+        assert!(a.try_extend_all(["a", "b"].map(std::string::String::from)).is_ok());
+        assert_eq!(&["x", "a", "b"], &*a);
+        let (consumed, rest) = a.try_extend_all(["c", "d", "e"].map(std::string::String::from))
+            .unwrap_err()
+            .element();
+        assert_eq!(&["x", "a", "b"], &*a);
+        assert_eq!(&["c"], &*consumed);
+        assert_eq!(rest.collect::<std::vec::Vec<_>>(), ["d", "e"]);
+    }
+
+    #[test]
+    fn extend() {
+        let mut a: ArrayVec<i32, 4> = ArrayVec::new();
+        a.extend(0..2);
+        a.extend(&[5, 6]);
+        assert_eq!(&[0, 1, 5, 6], &*a);
+
+        let (small, big): (ArrayVec<i32, 4>, ArrayVec<i32, 4>) =
+            a.iter().partition(|x| **x < 5);
+        assert_eq!(&[0, 1], &*small);
+        assert_eq!(&[5, 6], &*big);
+
+        let (xs, ys): (ArrayVec<i32, 2>, ArrayVec<char, 2>) =
+            [(1, 'a'), (2, 'b')].into_iter().unzip();
+        assert_eq!(&[1, 2], &*xs);
+        assert_eq!(&['a', 'b'], &*ys);
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_overflow() {
+        let mut a: ArrayVec<i32, 4> = ArrayVec::new();
+        a.extend(0..5);
+    }
+
+    #[test]
+    fn append_split_off() {
+        let mut a: ArrayVec<std::string::String, 4> = array_vec!["a".into()];
+        let mut b: ArrayVec<std::string::String, 8> = ArrayVec::new();
+        b.resize(3, "b".into());
+        a.append(&mut b);
+        assert_eq!(0, b.length());
+        assert_eq!(&["a", "b", "b", "b"], &*a);
+
+        b.resize(1, "c".into());
+        assert_eq!(Err(CapacityError::new(())), a.try_append(&mut b));
+        assert_eq!(1, b.length());
+        assert_eq!(4, a.length());
+
+        let tail = a.split_off(1);
+        assert_eq!(&["a"], &*a);
+        assert_eq!(&["b", "b", "b"], &*tail);
+        assert_eq!(0, a.split_off(1).length());
+    }
+
+    #[test]
+    fn retain_dedup() {
+        let mut a = array_vec![1, 1, 2, 3, 3, 3, 4, 1; 8];
+        a.dedup();
+        assert_eq!(&[1, 2, 3, 4, 1], &*a);
+        a.retain(|x| *x != 1);
+        assert_eq!(&[2, 3, 4], &*a);
+        a.retain_mut(|x| { *x *= 10; *x != 30 });
+        assert_eq!(&[20, 40], &*a);
+        a.extend_from_slice(&[41, 50, 55]);
+        a.dedup_by_key(|x| *x / 10);
+        assert_eq!(&[20, 40, 50], &*a);
+        a.dedup_by(|a, b| *a == *b + 10);
+        assert_eq!(&[20, 40], &*a);
+    }
+
+    #[test]
+    fn retain_panic_safety() {
+        let rc = std::rc::Rc::new(());
+        let mut a: ArrayVec<(i32, std::rc::Rc<()>), 6> = ArrayVec::new();
+        for i in 0..6 {
+            a.push((i, rc.clone())).unwrap();
+        }
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            a.retain(|x| {
+                assert!(x.0 != 3);
+                x.0 % 2 == 0
+            });
+        }));
+        assert!(result.is_err());
+        let kept: std::vec::Vec<i32> = a.iter().map(|x| x.0).collect();
+        assert_eq!(kept, [0, 2, 3, 4, 5]);
+        assert_eq!(6, std::rc::Rc::strong_count(&rc));
+    }
+
+    struct Bomb {
+        armed: bool,
+        _rc: std::rc::Rc<()>,
+    }
+
+    impl ops::Drop for Bomb {
+        fn drop(&mut self) {
+            if self.armed {
+                panic!("Bomb dropped");
+            }
+        }
+    }
+
+    fn bombs<const N: usize>(rc: &std::rc::Rc<()>, armed: usize) -> ArrayVec<Bomb, N> {
+        (0..N).map(|i| Bomb { armed: i == armed, _rc: rc.clone() }).collect()
+    }
+
+    #[test]
+    fn drop_panic_safety() {
+        let rc = std::rc::Rc::new(());
+        let a = bombs::<5>(&rc, 2);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(a)));
+        assert!(result.is_err());
+        assert_eq!(1, std::rc::Rc::strong_count(&rc));
+
+        let mut b = bombs::<5>(&rc, 3);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| b.truncate(1)));
+        assert!(result.is_err());
+        assert_eq!(1, b.len());
+        assert_eq!(2, std::rc::Rc::strong_count(&rc));
+        b.clear();
+        assert_eq!(1, std::rc::Rc::strong_count(&rc));
+    }
+
+    #[test]
+    fn clone() {
+        let mut a: ArrayVec<std::string::String, 4> = ArrayVec::new();
+        a.extend(["a", "b", "c"].map(std::string::String::from));
+        let b = a.clone();
+        assert_eq!(a, b);
+
+        let mut c: ArrayVec<std::string::String, 4> = array_vec!["x".into()];
+        c.clone_from(&a);
+        assert_eq!(a, c);
+        a.truncate(1);
+        c.clone_from(&a);
+        assert_eq!(&["a"], &*c);
+
+        let d: ArrayVec<i32, 4> = ArrayVec::from([1, 2, 3, 4]);
+        assert_eq!(d, d.clone());
+    }
+
+    #[test]
+    fn conversions() {
+        let a: ArrayVec<i32, 3> = [1, 2, 3].into();
+        assert_eq!(3, a.length());
+        let mut b = ArrayVec::<i32, 4>::try_from(a.get(..2).unwrap()).unwrap();
+        assert_eq!(&[1, 2], &*b);
+        assert!(ArrayVec::<i32, 2>::try_from(&*a).is_err());
+
+        fn sum<S: AsRef<[i32]>>(s: S) -> i32 { s.as_ref().iter().sum() }
+        assert_eq!(6, sum(&a));
+        b.as_mut()[0] = 5;
+        let borrowed: &[i32] = b.borrow();
+        assert_eq!(&[5, 2], borrowed);
+
+        let mut set = std::collections::HashSet::new();
+        set.insert(b);
+        assert!(set.contains(&[5, 2][..]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn vec_conversion() {
+        let a = ArrayVec::<std::string::String, 2>::try_from(vec!["a".into()]).unwrap();
+        assert_eq!(&["a"], &*a);
+        let v = vec![1, 2, 3];
+        assert_eq!(vec![1, 2, 3], ArrayVec::<i32, 2>::try_from(v).unwrap_err().element());
+    }
+
+    #[test]
+    fn array_vec_macro() {
+        const EMPTY: ArrayVec<u8, 3> = array_vec![];
+        static DIGITS: ArrayVec<u8, 16> = array_vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9,; 16];
+        assert_eq!(0, EMPTY.length());
+        assert_eq!(10, DIGITS.length());
+        assert_eq!(16, DIGITS.capacity());
+
+        let full: ArrayVec<std::string::String, 2> = array_vec!["a".into(), "b".into()];
+        assert_eq!(full, ["a", "b"]);
+    }
+
+    const fn countdown<const N: usize>() -> ArrayVec<usize, N> {
+        let mut a = ArrayVec::new();
+        while !a.is_full() {
+            if a.push(N - a.len()).is_err() {
+                panic!();
+            }
+        }
+        a
+    }
+
+    // Runs `f` on a thread whose stack is far smaller than the vectors it
+    // builds, so any copy of the embedded array through the stack overflows.
+    fn on_small_stack<F: FnOnce() + Send + 'static>(f: F) {
+        std::thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(f)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn init_in_place() {
+        on_small_stack(|| {
+            let mut slot = std::boxed::Box::<ArrayVec<u8, { 1 << 20 }, u32>>::new_uninit();
+            let a = ArrayVec::init_in_place(&mut slot);
+            assert!(a.is_empty());
+            a.extend_from_slice(&[1, 2, 3]);
+            let a = unsafe { slot.assume_init() };
+            assert_eq!(*a, [1, 2, 3]);
+            assert_eq!(1 << 20, a.capacity());
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn new_boxed() {
+        on_small_stack(|| {
+            let mut a = ArrayVec::<u64, { 1 << 18 }>::new_boxed();
+            a.resize(1 << 18, 7);
+            assert!(a.is_full());
+            assert_eq!(Some(7), a.pop());
+        });
+    }
+
+    #[test]
+    fn push_with() {
+        let mut a: ArrayVec<std::string::String, 2> = ArrayVec::new();
+        a.push_with(|| "a".into()).push('b');
+        assert_eq!(&["ab"], &*a);
+        assert!(a.try_push_with(|| "c".into()).is_ok());
+        let mut called = false;
+        assert!(a.try_push_with(|| { called = true; "d".into() }).is_err());
+        assert!(!called);
+        assert_eq!(2, a.len());
+
+        on_small_stack(|| {
+            let mut slot = std::boxed::Box::<ArrayVec<[u64; 1 << 14], 2>>::new_uninit();
+            let records = ArrayVec::init_in_place(&mut slot);
+            for i in 0..2 {
+                let record = unsafe {
+                    records.emplace_with(|r| r.as_mut_ptr().write_bytes(i, 1))
+                };
+                record[0] = 9;
+            }
+            assert_eq!([9, 0x0101_0101_0101_0101], [records[1][0], records[1][1]]);
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn emplace_full() {
+        let mut a: ArrayVec<u8, 0> = ArrayVec::new();
+        unsafe { a.emplace_with(|x| { x.write(1); }) };
+    }
+
+    #[test]
+    fn emplace_uninit() {
+        let mut a: ArrayVec<std::string::String, 2> = array_vec!["a".into()];
+        a.emplace_uninit().unwrap().write("b".into());
+        unsafe { a.set_len(2) };
+        assert_eq!(a, ["a", "b"]);
+        assert!(a.emplace_uninit().is_none());
+    }
+
+    #[test]
+    fn spare_capacity() {
+        let mut a: ArrayVec<std::string::String, 4> = array_vec!["a".into()];
+        let (init, spare) = a.split_at_spare_mut();
+        assert_eq!(3, spare.len());
+        for (slot, s) in spare.iter_mut().zip(["b", "c"]) {
+            slot.write(init[0].clone() + s);
+        }
+        unsafe { a.set_len(3) };
+        assert_eq!(a, ["a", "ab", "ac"]);
+
+        unsafe {
+            a.as_mut_ptr().add(3).write("d".into());
+            a.set_len(4);
+        }
+        assert!(a.spare_capacity_mut().is_empty());
+        assert_eq!("d", unsafe { &*a.as_ptr().add(3) });
+        a.truncate(0);
+        assert_eq!(4, a.spare_capacity_mut().len());
+    }
+
+    #[test]
+    fn const_operations() {
+        static ARRAY: ArrayVec<&str, 2> = ArrayVec::from_array(["a", "b"]);
+        const COUNTDOWN: ArrayVec<usize, 3> = countdown();
+        const POPPED: (Option<usize>, usize) = {
+            let mut a = countdown::<3>();
+            let result = (a.pop(), a.len());
+            // `ArrayVec` has a destructor, which cannot run at compile time.
+            mem::forget(a);
+            result
+        };
+        assert!(ARRAY.is_full());
+        assert_eq!(COUNTDOWN, [3, 2, 1]);
+        assert_eq!((Some(1), 2), POPPED);
+        assert!(ArrayVec::<u8, 1>::new().is_empty());
+    }
+
+    #[test]
+    fn len_types() {
+        let mut a: ArrayVec<u32, 254, u8> = ArrayVec::new();
+        for i in 0..254 {
+            a.push(i).unwrap();
+        }
+        assert!(a.is_full());
+        assert_eq!(253, a.pop().unwrap());
+        assert_eq!(253, a.len());
+
+        let mut b: ArrayVec<u16, 300, u16> = (0..300).collect();
+        b.truncate(256);
+        assert_eq!(256, b.len());
+        assert_eq!(Some(255), b.last().copied());
+
+        assert_eq!(254, u8::MAX_CAPACITY);
+        assert_eq!(mem::size_of::<ArrayVec<u8, 8, u8>>(),
+                   mem::size_of::<Option<ArrayVec<u8, 8, u8>>>());
+        assert_eq!(mem::size_of::<ArrayVec<u64, 4>>(),
+                   mem::size_of::<Option<ArrayVec<u64, 4>>>());
     }
 
     static mut DROPPINGS_DROPPED: bool = false;
@@ -225,7 +1850,7 @@ mod test {
 
     #[test]
     fn uninitialized_drop() {
-        let mut a: ArrayVec<Droppings, [_; 3]> = ArrayVec::new();
+        let mut a: ArrayVec<Droppings, 3> = ArrayVec::new();
         a.push(Droppings::new()).unwrap();
         a.push(Droppings::new()).unwrap();
         a.pop();
@@ -237,4 +1862,28 @@ mod test {
             assert!(DROPPINGS_DROPPED);
         }
     }
+
+    static mut DROP_ORDER: ArrayVec<u32, 8> = ArrayVec::new();
+
+    struct Ordered(u32);
+
+    impl ops::Drop for Ordered {
+        fn drop(&mut self) {
+            unsafe { (*ptr::addr_of_mut!(DROP_ORDER)).push(self.0).unwrap() };
+        }
+    }
+
+    #[test]
+    fn drop_order() {
+        let a: ArrayVec<Ordered, 4> = (1..=3).map(Ordered).collect();
+        let b: ArrayVec<Ordered, 4> = (4..=6).map(Ordered).collect();
+
+        // check whether elements are dropped in the same order as in a Vec
+        unsafe {
+            mem::drop(a);
+            assert_eq!(*ptr::addr_of!(DROP_ORDER), [1, 2, 3]);
+            b.drop_in_reverse();
+            assert_eq!(*ptr::addr_of!(DROP_ORDER), [1, 2, 3, 6, 5, 4]);
+        }
+    }
 }
@@ -0,0 +1,34 @@
+/// Creates an `ArrayVec` containing the given elements.
+///
+/// `array_vec![a, b, c; N]` creates an `ArrayVec` with capacity `N`, while
+/// `array_vec![a, b, c]` leaves the capacity to type inference. Passing more
+/// elements than fit is a compile-time error rather than a panic.
+///
+/// The macro can be evaluated at compile time, so it also initializes
+/// `static` and `const` items.
+///
+/// # Examples
+///
+/// ```
+/// use array_vec::*;
+/// static PRIMES: ArrayVec<u32, 8> = array_vec![2, 3, 5, 7; 8];
+/// assert_eq!(4, PRIMES.length());
+///
+/// let mut a: ArrayVec<&str, 4> = array_vec!["a", "b"];
+/// a.push("c").unwrap();
+/// assert_eq!(a, ["a", "b", "c"]);
+/// ```
+///
+/// ```compile_fail
+/// use array_vec::*;
+/// let a = array_vec![1, 2, 3; 2];
+/// ```
+#[macro_export]
+macro_rules! array_vec {
+    ($($x:expr),* $(,)? ; $cap:expr) => {
+        $crate::ArrayVec::<_, { $cap }>::__from_items([$($x),*])
+    };
+    ($($x:expr),* $(,)?) => {
+        $crate::ArrayVec::__from_items([$($x),*])
+    };
+}
@@ -0,0 +1,228 @@
+//! Overflow policies for `ArrayVec`.
+//!
+//! A policy decides what happens when an element is pushed onto a full
+//! vector. `PolicyVec` binds a policy to a buffer's type, so that its `push`
+//! handles overflow the same way at every call site instead of returning the
+//! `Result` of `ArrayVec::push`:
+//!
+//! ```
+//! use array_vec::PolicyVec;
+//! use array_vec::policy::EvictOldest;
+//!
+//! let mut samples: PolicyVec<u32, 3, EvictOldest> = PolicyVec::new();
+//! for x in 1..=5 {
+//!     samples.push(x);
+//! }
+//! assert_eq!(samples.into_inner(), [3, 4, 5]);
+//! ```
+//!
+//! `ArrayVec::push_or` applies a policy to a single push on a plain
+//! `ArrayVec`.
+
+use core::marker::PhantomData;
+use core::ops;
+use core::ptr;
+
+use crate::{ArrayVec, CapacityError, LenUint};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// What `PolicyVec::push` and `ArrayVec::push_or` do when the vector is full.
+///
+/// This trait is implemented by `Reject`, `DropNewest`, `EvictOldest` and
+/// `Panic`, and cannot be implemented outside of this crate.
+pub trait OverflowPolicy: sealed::Sealed {
+    /// The result of a push under this policy.
+    type Output<T>;
+
+    /// Appends `x` to `vec`, handling a full vector according to the policy.
+    #[doc(hidden)]
+    fn push<T, const N: usize, L: LenUint>(vec: &mut ArrayVec<T, N, L>, x: T)
+        -> Self::Output<T>;
+}
+
+/// Rejects the new element, handing it back in a `CapacityError`. This is
+/// what `ArrayVec::push` does.
+#[derive(Clone, Copy, Debug)]
+pub enum Reject {}
+
+/// Drops the new element and keeps the vector unchanged.
+#[derive(Clone, Copy, Debug)]
+pub enum DropNewest {}
+
+/// Removes the oldest (first) element, shifts the remaining elements to the
+/// left and appends the new one. The evicted element is returned.
+///
+/// A vector with a capacity of zero evicts the new element immediately.
+#[derive(Clone, Copy, Debug)]
+pub enum EvictOldest {}
+
+/// Panics.
+#[derive(Clone, Copy, Debug)]
+pub enum Panic {}
+
+impl sealed::Sealed for Reject {}
+impl sealed::Sealed for DropNewest {}
+impl sealed::Sealed for EvictOldest {}
+impl sealed::Sealed for Panic {}
+
+impl OverflowPolicy for Reject {
+    type Output<T> = Result<(), CapacityError<T>>;
+
+    fn push<T, const N: usize, L: LenUint>(vec: &mut ArrayVec<T, N, L>, x: T)
+        -> Result<(), CapacityError<T>>
+    {
+        vec.push(x)
+    }
+}
+
+impl OverflowPolicy for DropNewest {
+    type Output<T> = ();
+
+    fn push<T, const N: usize, L: LenUint>(vec: &mut ArrayVec<T, N, L>, x: T) {
+        let _ = vec.push(x);
+    }
+}
+
+impl OverflowPolicy for EvictOldest {
+    type Output<T> = Option<T>;
+
+    fn push<T, const N: usize, L: LenUint>(vec: &mut ArrayVec<T, N, L>, x: T) -> Option<T> {
+        let x = match vec.push(x) {
+            Ok(()) => return None,
+            Err(e) => e.element(),
+        };
+        if N == 0 {
+            return Some(x);
+        }
+        unsafe {
+            let base = vec.as_mut_ptr();
+            let oldest = ptr::read(base);
+            ptr::copy(base.add(1), base, N - 1);
+            ptr::write(base.add(N - 1), x);
+            Some(oldest)
+        }
+    }
+}
+
+impl OverflowPolicy for Panic {
+    type Output<T> = ();
+
+    fn push<T, const N: usize, L: LenUint>(vec: &mut ArrayVec<T, N, L>, x: T) {
+        if vec.push(x).is_err() {
+            panic!("push: this ArrayVec is full (capacity is {})", N);
+        }
+    }
+}
+
+/// An `ArrayVec` whose `push` handles overflow according to the policy `P`.
+///
+/// All other operations are available through `Deref` and `DerefMut` to the
+/// underlying `ArrayVec`.
+#[derive(Clone, Debug)]
+pub struct PolicyVec<T, const N: usize, P: OverflowPolicy, L: LenUint = usize> {
+    vec: ArrayVec<T, N, L>,
+    policy: PhantomData<P>,
+}
+
+impl<T, const N: usize, P: OverflowPolicy, L: LenUint> PolicyVec<T, N, P, L> {
+    /// Creates an empty `PolicyVec`.
+    pub const fn new() -> Self {
+        PolicyVec { vec: ArrayVec::new(), policy: PhantomData }
+    }
+
+    /// Appends an element, handling a full vector according to `P`.
+    pub fn push(&mut self, x: T) -> P::Output<T> {
+        P::push(&mut self.vec, x)
+    }
+
+    /// Returns the underlying `ArrayVec`.
+    pub fn into_inner(self) -> ArrayVec<T, N, L> {
+        self.vec
+    }
+}
+
+impl<T, const N: usize, P: OverflowPolicy, L: LenUint> Default for PolicyVec<T, N, P, L> {
+    fn default() -> Self {
+        PolicyVec::new()
+    }
+}
+
+impl<T, const N: usize, P: OverflowPolicy, L: LenUint> From<ArrayVec<T, N, L>>
+    for PolicyVec<T, N, P, L>
+{
+    fn from(vec: ArrayVec<T, N, L>) -> Self {
+        PolicyVec { vec, policy: PhantomData }
+    }
+}
+
+impl<T, const N: usize, P: OverflowPolicy, L: LenUint> ops::Deref for PolicyVec<T, N, P, L> {
+    type Target = ArrayVec<T, N, L>;
+
+    fn deref(&self) -> &ArrayVec<T, N, L> {
+        &self.vec
+    }
+}
+
+impl<T, const N: usize, P: OverflowPolicy, L: LenUint> ops::DerefMut
+    for PolicyVec<T, N, P, L>
+{
+    fn deref_mut(&mut self) -> &mut ArrayVec<T, N, L> {
+        &mut self.vec
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn reject_and_drop_newest() {
+        let mut a: ArrayVec<i32, 2> = array_vec![1, 2];
+        assert_eq!(3, a.push_or::<Reject>(3).unwrap_err().element());
+
+        let rc = Rc::new(());
+        let mut b: ArrayVec<Rc<()>, 1> = ArrayVec::new();
+        b.push_or::<DropNewest>(rc.clone());
+        b.push_or::<DropNewest>(rc.clone());
+        assert_eq!(1, b.len());
+        assert_eq!(2, Rc::strong_count(&rc));
+    }
+
+    #[test]
+    fn evict_oldest() {
+        let mut a: ArrayVec<i32, 3> = ArrayVec::new();
+        let evicted: std::vec::Vec<_> = (0..6).filter_map(|x| a.push_or::<EvictOldest>(x))
+                                             .collect();
+        assert_eq!(evicted, [0, 1, 2]);
+        assert_eq!(a, [3, 4, 5]);
+
+        let mut empty: ArrayVec<i32, 0> = ArrayVec::new();
+        assert_eq!(Some(7), empty.push_or::<EvictOldest>(7));
+    }
+
+    #[test]
+    fn policy_vec() {
+        let mut log: PolicyVec<i32, 2, DropNewest, u8> = array_vec![1].into();
+        log.push(2);
+        log.push(3);
+        assert_eq!(*log, [1, 2]);
+        assert_eq!(Some(2), log.pop());
+
+        let mut strict: PolicyVec<i32, 1, Reject> = PolicyVec::new();
+        assert!(strict.push(1).is_ok());
+        assert_eq!(2, strict.push(2).unwrap_err().element());
+        assert_eq!(strict.into_inner(), [1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic() {
+        let mut a: ArrayVec<i32, 1> = ArrayVec::new();
+        a.push_or::<Panic>(1);
+        a.push_or::<Panic>(2);
+    }
+}
@@ -0,0 +1,150 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ptr;
+
+use crate::{Drain, LenUint};
+
+/// A splicing iterator for `ArrayVec`, created by `ArrayVec::splice`.
+///
+/// It yields the removed elements. When it is dropped, the remaining removed
+/// elements are dropped and the replacement elements are inserted in their
+/// place.
+pub struct Splice<'a, I: Iterator, const N: usize, L: LenUint = usize> {
+    pub(crate) drain: Drain<'a, I::Item, N, L>,
+    pub(crate) replace_with: I,
+}
+
+impl<I: Iterator, const N: usize, L: LenUint> Splice<'_, I, N, L> {
+    /// Moves items from `replace_with` into the gap before the tail until
+    /// either the gap is full or the iterator is exhausted. Returns `true` if
+    /// the gap was filled.
+    fn fill(&mut self) -> bool {
+        unsafe {
+            let vec = self.drain.vec.as_mut();
+            while vec.len() < self.drain.tail_start {
+                match self.replace_with.next() {
+                    Some(x) => {
+                        // `store_len` reborrows the vector, so the pointer is
+                        // derived anew for every element.
+                        let len = vec.len();
+                        ptr::write(vec.as_mut_ptr().add(len), x);
+                        vec.store_len(len + 1);
+                    }
+                    None => return false,
+                }
+            }
+        }
+        true
+    }
+}
+
+impl<I: Iterator, const N: usize, L: LenUint> Iterator for Splice<'_, I, N, L> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<I: Iterator, const N: usize, L: LenUint> DoubleEndedIterator for Splice<'_, I, N, L> {
+    fn next_back(&mut self) -> Option<I::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<I: Iterator, const N: usize, L: LenUint> ExactSizeIterator for Splice<'_, I, N, L> {}
+
+impl<I: Iterator, const N: usize, L: LenUint> FusedIterator for Splice<'_, I, N, L> {}
+
+impl<I, const N: usize, L: LenUint> fmt::Debug for Splice<'_, I, N, L>
+    where I: Iterator, I::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Splice").field(&self.drain.as_slice()).finish()
+    }
+}
+
+impl<I: Iterator, const N: usize, L: LenUint> Drop for Splice<'_, I, N, L> {
+    fn drop(&mut self) {
+        self.drain.by_ref().for_each(drop);
+
+        // The length of the vector is kept up to date while filling, so the
+        // `Drain` moves the tail to the right place even if `replace_with`
+        // panics.
+        if !self.fill() {
+            return;
+        }
+        let first = match self.replace_with.next() {
+            Some(x) => x,
+            None => return,
+        };
+
+        // Make as much room as possible by moving the tail to the end of the
+        // array.
+        unsafe {
+            let vec = self.drain.vec.as_mut();
+            let base = vec.as_mut_ptr();
+            let new_tail_start = N - self.drain.tail_len;
+            ptr::copy(base.add(self.drain.tail_start), base.add(new_tail_start),
+                      self.drain.tail_len);
+            self.drain.tail_start = new_tail_start;
+            if vec.len() == new_tail_start {
+                drop(first);
+                panic!("splice: replacement does not fit (capacity is {})", N);
+            }
+            ptr::write(base.add(vec.len()), first);
+            vec.store_len(vec.len() + 1);
+        }
+        if self.fill() && self.replace_with.next().is_some() {
+            panic!("splice: replacement does not fit (capacity is {})", N);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ArrayVec, CapacityError};
+    use std::vec::Vec;
+
+    #[test]
+    fn splice_shorter() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5];
+        let removed: Vec<i32> = a.splice(1..4, [10]).collect();
+        assert_eq!(removed, [1, 2, 3]);
+        assert_eq!(&[0, 10, 4, 5], &*a);
+    }
+
+    #[test]
+    fn splice_longer() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5];
+        a.splice(1..3, [10, 11, 12, 13]);
+        assert_eq!(&[0, 10, 11, 12, 13, 3, 4, 5], &*a);
+        a.splice(.., []);
+        assert_eq!(0, a.length());
+    }
+
+    #[test]
+    fn splice_overflow_keeps_vector_consistent() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5];
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            a.splice(5.., [10, 11, 12, 13]);
+        }));
+        assert!(result.is_err());
+        assert_eq!(&[0, 1, 2, 3, 4, 10, 11, 12], &*a);
+    }
+
+    #[test]
+    fn try_splice() {
+        let mut a: ArrayVec<i32, 8> = array_vec![0, 1, 2, 3, 4, 5];
+        assert_eq!(Ok(()), a.try_splice(2..4, [7, 7, 7, 7]));
+        assert_eq!(&[0, 1, 7, 7, 7, 7, 4, 5], &*a);
+        assert_eq!(Err(CapacityError::new(9)), a.try_splice(..1, [8, 9]));
+        assert_eq!(&[0, 1, 7, 7, 7, 7, 4, 5], &*a);
+        assert_eq!(Ok(()), a.try_splice(..1, [8]));
+        assert_eq!(&[8, 1, 7, 7, 7, 7, 4, 5], &*a);
+    }
+}
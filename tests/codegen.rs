@@ -0,0 +1,111 @@
+//! Checks the code generated for operations that must not copy large values
+//! through the stack, and for those that must reduce to a `memcpy`.
+//!
+//! The probes in `examples/codegen_probe.rs` are compiled in release mode to
+//! LLVM IR, which is then searched for `memcpy` calls and for stack
+//! allocations and copies of at least `LARGE` bytes.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+
+const LARGE: u64 = 4096;
+
+fn emit_llvm_ir() -> String {
+    let target_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("codegen");
+    let examples = target_dir.join("release").join("examples");
+    let _ = fs::remove_dir_all(&examples);
+    let status = Command::new(env!("CARGO"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .args(["rustc", "--quiet", "--release", "--example", "codegen_probe",
+               "--features", "alloc", "--", "--emit=llvm-ir", "-Ccodegen-units=1"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let ll = fs::read_dir(&examples)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "ll"))
+        .unwrap();
+    fs::read_to_string(ll).unwrap()
+}
+
+// Returns the number in `line` that directly follows `prefix`.
+fn number_after(line: &str, prefix: &str) -> Option<u64> {
+    let start = line.find(prefix)? + prefix.len();
+    let digits: String = line[start..].chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+// Maps every defined function, and every alias of one, to the lines of its
+// body.
+fn functions(ir: &str) -> HashMap<String, Vec<&str>> {
+    let mut result: HashMap<String, Vec<&str>> = HashMap::new();
+    let mut aliases = Vec::new();
+    let mut current = None;
+    for line in ir.lines() {
+        if line.starts_with("define ") {
+            let name = line.split('@').nth(1).unwrap().split('(').next().unwrap();
+            current = Some(name.to_string());
+            result.entry(name.to_string()).or_default();
+        } else if line == "}" {
+            current = None;
+        } else if let (Some(name), Some(target)) = (line.strip_prefix('@'),
+                                                    line.split(" alias ").nth(1)) {
+            let name = name.split(' ').next().unwrap();
+            aliases.push((name.to_string(), target.rsplit('@').next().unwrap().to_string()));
+        } else if let Some(name) = &current {
+            result.get_mut(name).unwrap().push(line);
+        }
+    }
+    for (alias, target) in aliases {
+        let body = result.get(&target).cloned().unwrap_or_default();
+        result.insert(alias, body);
+    }
+    result
+}
+
+fn probe(name: &str) -> Vec<&'static str> {
+    static IR: OnceLock<String> = OnceLock::new();
+    let ir = IR.get_or_init(emit_llvm_ir);
+    functions(ir).remove(name).unwrap()
+}
+
+// Returns the sizes of the large `memcpy`s and stack allocations in `body`.
+fn large_copies(body: &[&str]) -> Vec<u64> {
+    body.iter()
+        .filter_map(|line| if line.contains("@llvm.memcpy") {
+            number_after(line, ", i64 ")
+        } else {
+            number_after(line, "alloca [")
+        })
+        .filter(|&size| size >= LARGE)
+        .collect()
+}
+
+fn calls_memcpy(body: &[&str]) -> bool {
+    body.iter().any(|line| line.contains("@llvm.memcpy"))
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_large_stack_copies() {
+    assert!(!large_copies(&probe("probe_moved_into_box")).is_empty(),
+            "the IR check does not detect large copies");
+    assert_eq!(large_copies(&probe("probe_init_in_place")), []);
+    assert_eq!(large_copies(&probe("probe_new_boxed")), []);
+    assert_eq!(large_copies(&probe("probe_emplace_with")), []);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn copy_types_use_memcpy() {
+    assert!(calls_memcpy(&probe("probe_extend_from_slice")));
+    assert!(calls_memcpy(&probe("probe_clone")));
+    let clone_from = probe("probe_clone_from");
+    assert!(calls_memcpy(&clone_from));
+    assert_eq!(large_copies(&clone_from), []);
+}